@@ -4,7 +4,7 @@
 //! By Intel Researche and Development"
 //! Adapation from <https://create.stephan-brumme.com/crc32/>
 //! LookUpTable generated with polynomial 0x04c11db7
-use core::mem::MaybeUninit;
+use crate::CrcWidth;
 
 /// Computes the CRC checksum for the specified buffer using the slicing by 8
 /// algorithm over 64 bit quantities.
@@ -19,8 +19,8 @@ use core::mem::MaybeUninit;
 /// assert_eq!(slice_by_8(HASH_ME, &my_lookup_table), 0x4C2750BD);
 /// ```
 #[inline(always)]
-pub fn slice_by_8(buf: &[u8], lookup_table: &[[u32; 256]; 8]) -> u32 {
-    slice_by_8_with_seed(buf, 0, lookup_table)
+pub fn slice_by_8<W: CrcWidth>(buf: &[u8], lookup_table: &[[W; 256]; 8]) -> W {
+    slice_by_8_with_seed(buf, W::ZERO, lookup_table)
 }
 
 /// Computes the CRC checksum for the specified buffer using the slicing by 8
@@ -35,37 +35,26 @@ pub fn slice_by_8(buf: &[u8], lookup_table: &[[u32; 256]; 8]) -> u32 {
 ///
 /// assert_eq!(slice_by_8_with_seed(HASH_ME, 123456789, &my_lookup_table), 0xEADB5034);
 /// ```
-pub fn slice_by_8_with_seed(buf: &[u8], seed: u32, lookup_table: &[[u32; 256]; 8]) -> u32 {
-    let mut crc = !seed;
+pub fn slice_by_8_with_seed<W: CrcWidth>(buf: &[u8], seed: W, lookup_table: &[[W; 256]; 8]) -> W {
+    !update(!seed, buf, lookup_table)
+}
 
+/// Updates the reflected CRC register with the specified buffer using the slicing by 8
+/// algorithm over 64 bit quantities.
+/// The register is neither inverted on entry nor on exit.
+pub(crate) fn update<W: CrcWidth>(mut crc: W, buf: &[u8], lookup_table: &[[W; 256]; 8]) -> W {
     // Consume all bits until we are 8 bits aligned
     let (prefix, shorts, suffix) = unsafe { buf.align_to::<u64>() };
     crc = prefix.iter().fold(crc, |acc, byte| {
-        lookup_table[0][((acc ^ *byte as u32) & 0xff) as usize] ^ (acc >> 8)
+        lookup_table[0][(acc.low_u64() as u8 ^ *byte) as usize] ^ acc.shift_right(8)
     });
 
     // Process eight bytes at once (Slicing-by-8)
-    #[cfg(target_endian = "big")]
-    let process_8_bytes_at_once = |acc: u32, byte: &u64| {
-        let byte = *byte;
-        let (low, high) = (
-            (byte as u32) ^ acc.reverse_bits(),
-            (byte >> u32::BITS) as u32,
-        );
-        lookup_table[0][(high & 0xFF) as usize]
-            ^ lookup_table[1][((high >> 8) & 0xFF) as usize]
-            ^ lookup_table[2][((high >> 16) & 0xFF) as usize]
-            ^ lookup_table[3][((high >> 24) & 0xFF) as usize]
-            ^ lookup_table[4][(low & 0xFF) as usize]
-            ^ lookup_table[5][((low >> 8) & 0xFF) as usize]
-            ^ lookup_table[6][((low >> 16) & 0xFF) as usize]
-            ^ lookup_table[7][((low >> 24) & 0xFF) as usize]
-    };
-
-    #[cfg(target_endian = "little")]
-    let process_8_bytes_at_once = |acc: u32, byte: &u64| {
-        let byte = *byte;
-        let (low, high) = ((byte as u32) ^ acc, (byte >> u32::BITS) as u32);
+    // The first byte in memory is the least significant one whatever the endianness is
+    // Registers wider than 64 bits keep their bits that are not shifted out
+    let process_8_bytes_at_once = |acc: W, byte: &u64| {
+        let byte = u64::from_le(*byte) ^ acc.low_u64();
+        let (low, high) = (byte as u32, (byte >> u32::BITS) as u32);
         lookup_table[0][((high >> 24) & 0xFF) as usize]
             ^ lookup_table[1][((high >> 16) & 0xFF) as usize]
             ^ lookup_table[2][((high >> 8) & 0xFF) as usize]
@@ -74,59 +63,336 @@ pub fn slice_by_8_with_seed(buf: &[u8], seed: u32, lookup_table: &[[u32; 256]; 8
             ^ lookup_table[5][((low >> 16) & 0xFF) as usize]
             ^ lookup_table[6][((low >> 8) & 0xFF) as usize]
             ^ lookup_table[7][(low & 0xFF) as usize]
+            ^ acc.shift_right(64)
     };
     crc = shorts.iter().fold(crc, process_8_bytes_at_once);
 
     // Consume remaining 1 to 7 bytes (standard algorithm)
-    !suffix.iter().fold(crc, |acc, byte| {
-        (acc >> 8) ^ lookup_table[0][((acc ^ *byte as u32) & 0xff) as usize]
+    suffix.iter().fold(crc, |acc, byte| {
+        acc.shift_right(8) ^ lookup_table[0][(acc.low_u64() as u8 ^ *byte) as usize]
     })
 }
 
-/// Generate a lookup table.
-/// The given polynomial is reversed before the generation
+/// Computes the non-reflected (MSB-first) CRC checksum for the specified buffer using the
+/// slicing by 8 algorithm over 64 bit quantities.
+/// The lookup table must be generated with [generate_normal_table].
 ///
 /// # Example
 /// ```
-/// use hud_slice_by_8::{crc32,generate_table};
+/// use hud_slice_by_8::{generate_normal_table, slice_by_8_normal};
 ///
-/// assert_eq!(generate_table(crc32::POLYNOMIAL), crc32::LOOKUP_TABLE);
+/// // CRC-32/BZIP2
+/// const LOOKUP_TABLE: [[u32; 256]; 8] = generate_normal_table(0x04C11DB7);
+///
+/// assert_eq!(slice_by_8_normal(b"123456789", &LOOKUP_TABLE), 0xFC891918);
 /// ```
-pub fn generate_table(polynomial: u32) -> [[u32; 256]; 8] {
-    let mut generated_lookup_table = MaybeUninit::<[[u32; 256]; 8]>::uninit();
-
-    // This implementation is not pleasant to read.
-    // A better version is above but is not compilable in constant expression for now
-    unsafe {
-        // Generate table 0
-        for (i, x) in generated_lookup_table.assume_init_mut()[0]
-            .iter_mut()
-            .enumerate()
-        {
-            *x = (0..8).fold(i as u32, |acc, _| {
-                (acc >> 1) ^ ((acc & 1) * polynomial.reverse_bits())
-            });
+#[inline(always)]
+pub fn slice_by_8_normal<W: CrcWidth>(buf: &[u8], lookup_table: &[[W; 256]; 8]) -> W {
+    slice_by_8_normal_with_seed(buf, W::ZERO, lookup_table)
+}
+
+/// Computes the non-reflected (MSB-first) CRC checksum for the specified buffer using the
+/// slicing by 8 algorithm over 64 bit quantities, adding a seed to the result.
+/// The lookup table must be generated with [generate_normal_table].
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{generate_normal_table, slice_by_8_normal, slice_by_8_normal_with_seed};
+///
+/// const LOOKUP_TABLE: [[u32; 256]; 8] = generate_normal_table(0x04C11DB7);
+/// let seed = slice_by_8_normal(b"12345", &LOOKUP_TABLE);
+///
+/// assert_eq!(slice_by_8_normal_with_seed(b"6789", seed, &LOOKUP_TABLE), 0xFC891918);
+/// ```
+pub fn slice_by_8_normal_with_seed<W: CrcWidth>(
+    buf: &[u8],
+    seed: W,
+    lookup_table: &[[W; 256]; 8],
+) -> W {
+    !update_normal(!seed, buf, lookup_table)
+}
+
+/// Updates the non-reflected CRC register with the specified buffer using the slicing by 8
+/// algorithm over 64 bit quantities.
+/// The register is neither inverted on entry nor on exit.
+pub(crate) fn update_normal<W: CrcWidth>(
+    mut crc: W,
+    buf: &[u8],
+    lookup_table: &[[W; 256]; 8],
+) -> W {
+    // Consume all bits until we are 8 bits aligned
+    let (prefix, shorts, suffix) = unsafe { buf.align_to::<u64>() };
+    crc = prefix.iter().fold(crc, |acc, byte| {
+        lookup_table[0][((acc.high_u64() >> 56) as u8 ^ *byte) as usize] ^ acc.shift_left(8)
+    });
+
+    // Process eight bytes at once (Slicing-by-8)
+    // The first byte in memory is the most significant one whatever the endianness is
+    // Registers wider than 64 bits keep their bits that are not shifted out
+    let process_8_bytes_at_once = |acc: W, byte: &u64| {
+        let byte = u64::from_be(*byte) ^ acc.high_u64();
+        let (high, low) = ((byte >> u32::BITS) as u32, byte as u32);
+        lookup_table[0][(low & 0xFF) as usize]
+            ^ lookup_table[1][((low >> 8) & 0xFF) as usize]
+            ^ lookup_table[2][((low >> 16) & 0xFF) as usize]
+            ^ lookup_table[3][((low >> 24) & 0xFF) as usize]
+            ^ lookup_table[4][(high & 0xFF) as usize]
+            ^ lookup_table[5][((high >> 8) & 0xFF) as usize]
+            ^ lookup_table[6][((high >> 16) & 0xFF) as usize]
+            ^ lookup_table[7][((high >> 24) & 0xFF) as usize]
+            ^ acc.shift_left(64)
+    };
+    crc = shorts.iter().fold(crc, process_8_bytes_at_once);
+
+    // Consume remaining 1 to 7 bytes (standard algorithm)
+    suffix.iter().fold(crc, |acc, byte| {
+        acc.shift_left(8) ^ lookup_table[0][((acc.high_u64() >> 56) as u8 ^ *byte) as usize]
+    })
+}
+
+/// Generate the reflected and non-reflected lookup table generators of a register type.
+/// Generators are `const fn` so they can not be generic over [CrcWidth].
+/// `$reflected_tables` generates any number of reflected tables for the other slicing variants.
+macro_rules! generate_table_fns {
+    (
+        $ty:ty,
+        $reflected_tables:ident,
+        $(#[$reflected_meta:meta])* $reflected:ident,
+        $(#[$normal_meta:meta])* $normal:ident
+    ) => {
+        $(#[$reflected_meta])*
+        pub const fn $reflected(polynomial: $ty) -> [[$ty; 256]; 8] {
+            $reflected_tables(polynomial)
         }
 
-        // Generate table 1..=7
-        let table_0 = &generated_lookup_table.assume_init()[0];
-        for i in 1..=7 {
-            let table_before = &generated_lookup_table.assume_init()[i - 1];
-            for (i, x) in generated_lookup_table.assume_init_mut()[i]
-                .iter_mut()
-                .enumerate()
-            {
-                *x = (table_before[i] >> 8) ^ table_0[(table_before[i] & 0xFF) as usize];
+        /// Generate `N` reflected lookup tables.
+        /// The given polynomial is reversed before the generation
+        pub(crate) const fn $reflected_tables<const N: usize>(polynomial: $ty) -> [[$ty; 256]; N] {
+            let reversed_polynomial = polynomial.reverse_bits();
+            let mut generated_lookup_table = [[0; 256]; N];
+
+            // Generate table 0
+            let mut i = 0;
+            while i < 256 {
+                let mut crc = i as $ty;
+                let mut bit = 0;
+                while bit < 8 {
+                    crc = (crc >> 1) ^ ((crc & 1) * reversed_polynomial);
+                    bit += 1;
+                }
+                generated_lookup_table[0][i] = crc;
+                i += 1;
             }
+
+            // Generate table 1..N
+            // 8 bit registers are entirely shifted out by each byte
+            let mut table = 1;
+            while table < N {
+                let mut i = 0;
+                while i < 256 {
+                    let previous = generated_lookup_table[table - 1][i];
+                    let shifted = match previous.checked_shr(8) {
+                        Some(shifted) => shifted,
+                        None => 0,
+                    };
+                    generated_lookup_table[table][i] =
+                        shifted ^ generated_lookup_table[0][(previous & 0xFF) as usize];
+                    i += 1;
+                }
+                table += 1;
+            }
+            generated_lookup_table
         }
-        *generated_lookup_table.assume_init_mut()
-    }
+
+        $(#[$normal_meta])*
+        pub const fn $normal(polynomial: $ty) -> [[$ty; 256]; 8] {
+            let mut generated_lookup_table = [[0; 256]; 8];
+
+            // Generate table 0
+            let mut i = 0;
+            while i < 256 {
+                let mut crc = (i as $ty) << (<$ty>::BITS - 8);
+                let mut bit = 0;
+                while bit < 8 {
+                    crc = (crc << 1) ^ ((crc >> (<$ty>::BITS - 1)) * polynomial);
+                    bit += 1;
+                }
+                generated_lookup_table[0][i] = crc;
+                i += 1;
+            }
+
+            // Generate table 1..=7
+            // 8 bit registers are entirely shifted out by each byte
+            let mut table = 1;
+            while table < 8 {
+                let mut i = 0;
+                while i < 256 {
+                    let previous = generated_lookup_table[table - 1][i];
+                    let shifted = match previous.checked_shl(8) {
+                        Some(shifted) => shifted,
+                        None => 0,
+                    };
+                    generated_lookup_table[table][i] = shifted
+                        ^ generated_lookup_table[0][(previous >> (<$ty>::BITS - 8)) as usize];
+                    i += 1;
+                }
+                table += 1;
+            }
+            generated_lookup_table
+        }
+    };
 }
 
+generate_table_fns!(
+    u32,
+    generate_tables,
+    /// Generate a lookup table.
+    /// The given polynomial is reversed before the generation
+    ///
+    /// The function is `const` so a lookup table for any polynomial can be built at compile time.
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::{crc32,generate_table};
+    ///
+    /// const MY_LOOKUP_TABLE: [[u32; 256]; 8] = generate_table(0x741B8CD7);
+    ///
+    /// assert_eq!(generate_table(crc32::POLYNOMIAL), crc32::LOOKUP_TABLE);
+    /// ```
+    generate_table,
+    /// Generate a non-reflected (MSB-first) lookup table.
+    /// Unlike [generate_table], the given polynomial is used as is.
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::generate_normal_table;
+    ///
+    /// const LOOKUP_TABLE: [[u32; 256]; 8] = generate_normal_table(0x04C11DB7);
+    ///
+    /// assert_eq!(LOOKUP_TABLE[0][1], 0x04C11DB7);
+    /// ```
+    generate_normal_table
+);
+
+generate_table_fns!(
+    u8,
+    generate_tables_u8,
+    /// Generate a 8 bit lookup table.
+    /// The given polynomial is reversed before the generation
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::generate_table_u8;
+    ///
+    /// const LOOKUP_TABLE: [[u8; 256]; 8] = generate_table_u8(0x31);
+    ///
+    /// assert_eq!(LOOKUP_TABLE[0][1], 0x5E);
+    /// ```
+    generate_table_u8,
+    /// Generate a non-reflected (MSB-first) 8 bit lookup table.
+    /// Unlike [generate_table_u8], the given polynomial is used as is.
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::generate_normal_table_u8;
+    ///
+    /// const LOOKUP_TABLE: [[u8; 256]; 8] = generate_normal_table_u8(0x07);
+    ///
+    /// assert_eq!(LOOKUP_TABLE[0][1], 0x07);
+    /// ```
+    generate_normal_table_u8
+);
+
+generate_table_fns!(
+    u16,
+    generate_tables_u16,
+    /// Generate a 16 bit lookup table.
+    /// The given polynomial is reversed before the generation
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::generate_table_u16;
+    ///
+    /// const LOOKUP_TABLE: [[u16; 256]; 8] = generate_table_u16(0x8005);
+    ///
+    /// assert_eq!(LOOKUP_TABLE[0][1], 0xC0C1);
+    /// ```
+    generate_table_u16,
+    /// Generate a non-reflected (MSB-first) 16 bit lookup table.
+    /// Unlike [generate_table_u16], the given polynomial is used as is.
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::generate_normal_table_u16;
+    ///
+    /// const LOOKUP_TABLE: [[u16; 256]; 8] = generate_normal_table_u16(0x1021);
+    ///
+    /// assert_eq!(LOOKUP_TABLE[0][1], 0x1021);
+    /// ```
+    generate_normal_table_u16
+);
+
+generate_table_fns!(
+    u64,
+    generate_tables_u64,
+    /// Generate a 64 bit lookup table.
+    /// The given polynomial is reversed before the generation
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::{crc64, generate_table_u64};
+    ///
+    /// assert_eq!(generate_table_u64(crc64::POLYNOMIAL), crc64::LOOKUP_TABLE);
+    /// ```
+    generate_table_u64,
+    /// Generate a non-reflected (MSB-first) 64 bit lookup table.
+    /// Unlike [generate_table_u64], the given polynomial is used as is.
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::{crc64, generate_normal_table_u64};
+    ///
+    /// const LOOKUP_TABLE: [[u64; 256]; 8] = generate_normal_table_u64(crc64::POLYNOMIAL);
+    ///
+    /// assert_eq!(LOOKUP_TABLE[0][1], crc64::POLYNOMIAL);
+    /// ```
+    generate_normal_table_u64
+);
+
+generate_table_fns!(
+    u128,
+    generate_tables_u128,
+    /// Generate a 128 bit lookup table.
+    /// The given polynomial is reversed before the generation
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::generate_table_u128;
+    ///
+    /// const LOOKUP_TABLE: [[u128; 256]; 8] = generate_table_u128(1 << 127 | 1);
+    ///
+    /// assert_eq!(LOOKUP_TABLE[0][128], 1 << 127 | 1);
+    /// ```
+    generate_table_u128,
+    /// Generate a non-reflected (MSB-first) 128 bit lookup table.
+    /// Unlike [generate_table_u128], the given polynomial is used as is.
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::generate_normal_table_u128;
+    ///
+    /// const LOOKUP_TABLE: [[u128; 256]; 8] = generate_normal_table_u128(0x87);
+    ///
+    /// assert_eq!(LOOKUP_TABLE[0][1], 0x87);
+    /// ```
+    generate_normal_table_u128
+);
+
 #[cfg(test)]
 mod tests {
     use crate as slice_by_8;
     use slice_by_8::crc32::LOOKUP_TABLE;
+    use slice_by_8::CrcWidth;
 
     #[test]
     fn slice_by_8_no_seed() {
@@ -142,4 +408,71 @@ mod tests {
             0xEADB5034
         );
     }
+
+    #[test]
+    fn generate_table_is_usable_in_const_context() {
+        const TABLE: [[u32; 256]; 8] = slice_by_8::generate_table(slice_by_8::crc32::POLYNOMIAL);
+        assert_eq!(TABLE, slice_by_8::generate_table(slice_by_8::crc32::POLYNOMIAL));
+        assert_eq!(TABLE[0][1], 0x77073096);
+        assert_eq!(TABLE[7][255], 0x264b06e6);
+    }
+
+    #[test]
+    fn slice_by_8_normal_is_coherent_with_bytewise_algorithm() {
+        const LOOKUP_TABLE: [[u32; 256]; 8] = slice_by_8::generate_normal_table(0x04C11DB7);
+        const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog, 0123456789";
+        for start in 0..8 {
+            for end in start..HASH_ME.len() {
+                let buf = &HASH_ME[start..end];
+                let bytewise = !buf.iter().fold(!0u32, |acc, byte| {
+                    (acc << 8) ^ LOOKUP_TABLE[0][((acc >> 24) ^ *byte as u32) as usize]
+                });
+                assert_eq!(slice_by_8::slice_by_8_normal(buf, &LOOKUP_TABLE), bytewise);
+            }
+        }
+    }
+
+    #[test]
+    fn update_normal_u64_is_coherent_with_bytewise_algorithm() {
+        const LOOKUP_TABLE: [[u64; 256]; 8] =
+            slice_by_8::generate_normal_table_u64(slice_by_8::crc64::POLYNOMIAL);
+        const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog, 0123456789";
+        for start in 0..8 {
+            for end in start..HASH_ME.len() {
+                let buf = &HASH_ME[start..end];
+                let bytewise = buf.iter().fold(!0u64, |acc, byte| {
+                    (acc << 8) ^ LOOKUP_TABLE[0][((acc >> 56) ^ *byte as u64) as usize]
+                });
+                assert_eq!(super::update_normal(!0, buf, &LOOKUP_TABLE), bytewise);
+            }
+        }
+    }
+
+    #[test]
+    fn every_width_is_coherent_with_bytewise_algorithm() {
+        fn check<W: CrcWidth>(polynomial: W) {
+            const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog, 0123456789";
+            let reflected = W::generate_table(polynomial);
+            let normal = W::generate_normal_table(polynomial);
+            for start in 0..8 {
+                for end in start..HASH_ME.len() {
+                    let buf = &HASH_ME[start..end];
+                    let bytewise = buf.iter().fold(!W::ZERO, |acc, byte| {
+                        reflected[0][(acc.low_u64() as u8 ^ *byte) as usize] ^ acc.shift_right(8)
+                    });
+                    assert_eq!(super::update(!W::ZERO, buf, &reflected), bytewise);
+                    let bytewise = buf.iter().fold(!W::ZERO, |acc, byte| {
+                        normal[0][((acc.high_u64() >> 56) as u8 ^ *byte) as usize]
+                            ^ acc.shift_left(8)
+                    });
+                    assert_eq!(super::update_normal(!W::ZERO, buf, &normal), bytewise);
+                }
+            }
+        }
+        check(0x07u8);
+        check(0x1021u16);
+        check(0x04C11DB7u32);
+        check(0x42F0E1EBA9EA3693u64);
+        check((0x42F0E1EBA9EA3693u128 << 64) | 0x04C11DB7);
+    }
 }
@@ -0,0 +1,250 @@
+//!
+//! Asynchronous adapters shared by the CRC32 and CRC32c modules.
+use crate::{Digest, Kernel};
+use core::fmt::{self, Debug};
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use std::io::Result;
+
+/// Asynchronous writer computing the CRC32 checksum of the [Kernel] over the data written to the inner writer
+///
+/// Implements `AsyncWrite` of `tokio` and of `futures-io` when the inner writer does,
+/// the inner writer must be [Unpin] (use [Box::pin](std::boxed::Box::pin) otherwise).
+pub struct AsyncCrcWriter<W, K> {
+    inner: W,
+    digest: Digest<K>,
+}
+
+impl<W: Debug, K> Debug for AsyncCrcWriter<W, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncCrcWriter")
+            .field("inner", &self.inner)
+            .field("digest", &self.digest)
+            .finish()
+    }
+}
+
+impl<W, K: Kernel> AsyncCrcWriter<W, K> {
+    /// Create a new [AsyncCrcWriter] forwarding to the inner writer
+    pub fn new(inner: W) -> AsyncCrcWriter<W, K> {
+        AsyncCrcWriter {
+            inner,
+            digest: Digest::new(),
+        }
+    }
+
+    /// Returns the checksum of the data written so far
+    pub fn crc(&self) -> u32 {
+        self.digest.finalize()
+    }
+
+    /// Returns a reference to the inner writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    /// Data written directly to the inner writer is not checksummed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the [AsyncCrcWriter] and returns the inner writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes to the inner writer and checksums the written data
+    fn poll_write_with(
+        &mut self,
+        poll_write: impl FnOnce(Pin<&mut W>) -> Poll<Result<usize>>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>>
+    where
+        W: Unpin,
+    {
+        let written = ready!(poll_write(Pin::new(&mut self.inner)))?;
+        self.digest.update(&buf[..written]);
+        Poll::Ready(Ok(written))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<W: tokio::io::AsyncWrite + Unpin, K: Kernel> tokio::io::AsyncWrite for AsyncCrcWriter<W, K> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        self.get_mut()
+            .poll_write_with(|inner| inner.poll_write(cx, buf), buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl<W: futures_io::AsyncWrite + Unpin, K: Kernel> futures_io::AsyncWrite for AsyncCrcWriter<W, K> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        self.get_mut()
+            .poll_write_with(|inner| inner.poll_write(cx, buf), buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
+
+/// Asynchronous reader computing the CRC32 checksum of the [Kernel] over the data read from the inner reader
+///
+/// Implements `AsyncRead` of `tokio` and of `futures-io` when the inner reader does,
+/// the inner reader must be [Unpin] (use [Box::pin](std::boxed::Box::pin) otherwise).
+pub struct AsyncCrcReader<R, K> {
+    inner: R,
+    digest: Digest<K>,
+}
+
+impl<R: Debug, K> Debug for AsyncCrcReader<R, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncCrcReader")
+            .field("inner", &self.inner)
+            .field("digest", &self.digest)
+            .finish()
+    }
+}
+
+impl<R, K: Kernel> AsyncCrcReader<R, K> {
+    /// Create a new [AsyncCrcReader] forwarding to the inner reader
+    pub fn new(inner: R) -> AsyncCrcReader<R, K> {
+        AsyncCrcReader {
+            inner,
+            digest: Digest::new(),
+        }
+    }
+
+    /// Returns the checksum of the data read so far
+    pub fn crc(&self) -> u32 {
+        self.digest.finalize()
+    }
+
+    /// Returns a reference to the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    /// Data read directly from the inner reader is not checksummed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the [AsyncCrcReader] and returns the inner reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R: tokio::io::AsyncRead + Unpin, K: Kernel> tokio::io::AsyncRead for AsyncCrcReader<R, K> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        let filled = buf.filled().len();
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.digest.update(&buf.filled()[filled..]);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl<R: futures_io::AsyncRead + Unpin, K: Kernel> futures_io::AsyncRead for AsyncCrcReader<R, K> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        let this = self.get_mut();
+        let read = ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.digest.update(&buf[..read]);
+        Poll::Ready(Ok(read))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsyncCrcReader, AsyncCrcWriter};
+    use crate::crc32::CRC32Kernel;
+    use crate::crc32c::CRC32CKernel;
+    use crate::{crc32, crc32c, Kernel};
+
+    const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog, 0123456789";
+
+    #[cfg(feature = "tokio")]
+    fn tokio_duplex_checksums_both_ends<K: Kernel>(slice_by_8: fn(&[u8]) -> u32) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let (client, server) = tokio::io::duplex(16);
+            let mut writer = AsyncCrcWriter::<_, K>::new(client);
+            let mut reader = AsyncCrcReader::<_, K>::new(server);
+
+            let write = async {
+                for chunk in HASH_ME.chunks(7) {
+                    writer.write_all(chunk).await.unwrap();
+                }
+                writer.shutdown().await.unwrap();
+            };
+            let read = async {
+                let mut buf = std::vec::Vec::new();
+                reader.read_to_end(&mut buf).await.unwrap();
+                buf
+            };
+            let ((), buf) = tokio::join!(write, read);
+
+            assert_eq!(buf, HASH_ME);
+            assert_eq!(writer.crc(), slice_by_8(HASH_ME));
+            assert_eq!(reader.crc(), slice_by_8(HASH_ME));
+        });
+    }
+
+    #[cfg(feature = "futures-io")]
+    fn futures_io_copy_checksums_both_ends<K: Kernel>(slice_by_8: fn(&[u8]) -> u32) {
+        use futures::io::{copy, Cursor};
+
+        futures::executor::block_on(async {
+            let mut reader = AsyncCrcReader::<_, K>::new(Cursor::new(HASH_ME));
+            let mut writer = AsyncCrcWriter::<_, K>::new(Cursor::new(std::vec::Vec::new()));
+            copy(&mut reader, &mut writer).await.unwrap();
+
+            assert_eq!(writer.get_ref().get_ref(), HASH_ME);
+            assert_eq!(writer.crc(), slice_by_8(HASH_ME));
+            assert_eq!(reader.crc(), slice_by_8(HASH_ME));
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "tokio")]
+    fn tokio_adapters_are_coherent_with_slice_by_8() {
+        tokio_duplex_checksums_both_ends::<CRC32Kernel>(crc32::slice_by_8);
+        tokio_duplex_checksums_both_ends::<CRC32CKernel>(crc32c::slice_by_8);
+    }
+
+    #[test]
+    #[cfg(feature = "futures-io")]
+    fn futures_io_adapters_are_coherent_with_slice_by_8() {
+        futures_io_copy_checksums_both_ends::<CRC32Kernel>(crc32::slice_by_8);
+        futures_io_copy_checksums_both_ends::<CRC32CKernel>(crc32c::slice_by_8);
+    }
+}
@@ -0,0 +1,603 @@
+//!
+//! Shared implementation of the `crc32sum` and `crc32csum` binaries.
+//! Prints or checks CRC checksums of files and standard input in the `sha256sum` format.
+use hud_slice_by_8::{crc32, crc32c, Crc, CrcParams};
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Size of the buffer the inputs are read through
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Models of the catalogue selectable with `--model`
+const CATALOGUE: &[(&str, CrcParams)] = &[
+    ("aixm", crc32::aixm::PARAMS),
+    ("autosar", crc32::autosar::PARAMS),
+    ("base91-d", crc32::base91_d::PARAMS),
+    ("bzip2", crc32::bzip2::PARAMS),
+    ("cd-rom-edc", crc32::cd_rom_edc::PARAMS),
+    ("cksum", crc32::cksum::PARAMS),
+    ("jamcrc", crc32::jamcrc::PARAMS),
+    ("mpeg-2", crc32::mpeg_2::PARAMS),
+    ("xfer", crc32::xfer::PARAMS),
+];
+
+/// CRC computed by the binary
+pub(crate) enum Model {
+    /// CRC-32/ISO-HDLC computed by [crc32::slice_by_8]
+    Crc32,
+    /// CRC-32/ISCSI computed by [crc32c::slice_by_8]
+    Crc32c,
+    /// Any model of the catalogue
+    Catalogued(Box<Crc>),
+}
+
+impl Model {
+    /// Finds the model by name, ignoring the case and accepting `_` for `-`
+    fn from_name(name: &str) -> Option<Model> {
+        let name = name.to_ascii_lowercase().replace('_', "-");
+        match name.as_str() {
+            "crc32" | "crc-32" | "iso-hdlc" => Some(Model::Crc32),
+            "crc32c" | "crc-32c" | "iscsi" | "castagnoli" => Some(Model::Crc32c),
+            _ => CATALOGUE
+                .iter()
+                .find(|(model, _)| *model == name)
+                .map(|(_, params)| Model::Catalogued(Box::new(Crc::new(*params)))),
+        }
+    }
+
+    /// Computes the checksum of everything read from the reader
+    fn checksum(&self, reader: &mut dyn Read) -> io::Result<u32> {
+        let mut buf = vec![0u8; BUFFER_SIZE];
+        let mut crc = match self {
+            Model::Crc32 | Model::Crc32c => 0,
+            Model::Catalogued(engine) => engine.init(),
+        };
+        loop {
+            let read = match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            let chunk = &buf[..read];
+            crc = match self {
+                Model::Crc32 => crc32::slice_by_8_with_seed(chunk, crc),
+                Model::Crc32c => crc32c::slice_by_8_with_seed(chunk, crc),
+                Model::Catalogued(engine) => engine.update(crc, chunk),
+            };
+        }
+        Ok(match self {
+            Model::Crc32 | Model::Crc32c => crc,
+            Model::Catalogued(engine) => engine.finalize(crc),
+        })
+    }
+}
+
+/// Options of the command line
+struct Options {
+    check: bool,
+    decimal: bool,
+    quiet: bool,
+    recursive: bool,
+    model: Model,
+    files: Vec<PathBuf>,
+}
+
+/// Outcome of the parsing of the command line
+enum Command {
+    Run(Options),
+    Help,
+}
+
+/// Parses the arguments that follow the program name
+fn parse_args(
+    args: impl IntoIterator<Item = OsString>,
+    default_model: Model,
+) -> Result<Command, String> {
+    let mut options = Options {
+        check: false,
+        decimal: false,
+        quiet: false,
+        recursive: false,
+        model: default_model,
+        files: Vec::new(),
+    };
+    let model = |name: String| Model::from_name(&name).ok_or(format!("unknown model '{name}'"));
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let Some(flag) = arg
+            .to_str()
+            .filter(|arg| arg.starts_with('-') && *arg != "-")
+        else {
+            options.files.push(arg.into());
+            continue;
+        };
+        if flag == "--" {
+            options.files.extend(args.by_ref().map(PathBuf::from));
+        } else if let Some(long) = flag.strip_prefix("--") {
+            let (long, value) = match long.split_once('=') {
+                Some((long, value)) => (long, Some(value.to_owned())),
+                None => (long, None),
+            };
+            match long {
+                "check" => options.check = true,
+                "dec" => options.decimal = true,
+                "hex" => options.decimal = false,
+                "quiet" => options.quiet = true,
+                "recursive" => options.recursive = true,
+                "help" => return Ok(Command::Help),
+                "model" => {
+                    let name = value
+                        .or_else(|| args.next().and_then(|name| name.into_string().ok()))
+                        .ok_or("option '--model' requires a model name")?;
+                    options.model = model(name)?;
+                }
+                _ => return Err(format!("unrecognized option '--{long}'")),
+            }
+        } else {
+            // Short options may be grouped, the model name follows `-m` or is the next argument
+            for (i, short) in flag.char_indices().skip(1) {
+                match short {
+                    'c' => options.check = true,
+                    'q' => options.quiet = true,
+                    'r' => options.recursive = true,
+                    'h' => return Ok(Command::Help),
+                    'm' => {
+                        let name = Some(&flag[i + 1..])
+                            .filter(|name| !name.is_empty())
+                            .map(str::to_owned)
+                            .or_else(|| args.next().and_then(|name| name.into_string().ok()))
+                            .ok_or("option '-m' requires a model name")?;
+                        options.model = model(name)?;
+                        break;
+                    }
+                    _ => return Err(format!("invalid option -- '{short}'")),
+                }
+            }
+        }
+    }
+    if options.files.is_empty() {
+        options.files.push(PathBuf::from("-"));
+    }
+    Ok(Command::Run(options))
+}
+
+/// Formats the checksum in hexadecimal or decimal
+fn format_checksum(crc: u32, decimal: bool) -> String {
+    if decimal {
+        crc.to_string()
+    } else {
+        format!("{crc:08x}")
+    }
+}
+
+/// Splits a line of a checksum list into its checksum and path.
+/// Lines are `<checksum>  <path>`, a `*` may replace the second space as in binary mode.
+fn parse_check_line(line: &str, decimal: bool) -> Option<(u32, &str)> {
+    let (checksum, path) = line.split_once(' ')?;
+    let path = path.strip_prefix([' ', '*'])?;
+    if path.is_empty() {
+        return None;
+    }
+    let crc = if decimal {
+        checksum.parse().ok()?
+    } else if checksum.len() == 8 {
+        u32::from_str_radix(checksum, 16).ok()?
+    } else {
+        return None;
+    };
+    Some((crc, path))
+}
+
+/// Calls `hash` with the reader of the path, reading standard input for `-`
+fn open_input<T>(path: &Path, hash: impl FnOnce(&mut dyn Read) -> io::Result<T>) -> io::Result<T> {
+    if path == Path::new("-") {
+        hash(&mut io::stdin().lock())
+    } else {
+        hash(&mut File::open(path)?)
+    }
+}
+
+/// Runs the binary with the arguments that follow the program name and returns its exit code.
+/// A failure to write the output, such as a closed pipe or a full disk, ends the run with code 1.
+pub(crate) fn run(
+    program: &str,
+    default_model: Model,
+    args: impl IntoIterator<Item = OsString>,
+    stdout: &mut dyn Write,
+    stderr: &mut dyn Write,
+) -> i32 {
+    match try_run(program, default_model, args, stdout, stderr) {
+        Ok(code) => code,
+        Err(error) => {
+            // Standard error may be the output that failed, the exit code reports the failure anyway
+            let _ = writeln!(stderr, "{program}: write error: {error}");
+            1
+        }
+    }
+}
+
+/// Runs the binary and returns its exit code, or the error of the output that could not be written
+fn try_run(
+    program: &str,
+    default_model: Model,
+    args: impl IntoIterator<Item = OsString>,
+    stdout: &mut dyn Write,
+    stderr: &mut dyn Write,
+) -> io::Result<i32> {
+    let options = match parse_args(args, default_model) {
+        Ok(Command::Run(options)) => options,
+        Ok(Command::Help) => {
+            write!(stdout, "{}", usage(program))?;
+            stdout.flush()?;
+            return Ok(0);
+        }
+        Err(error) => {
+            writeln!(stderr, "{program}: {error}")?;
+            writeln!(stderr, "Try '{program} --help' for more information.")?;
+            return Ok(1);
+        }
+    };
+    let mut cli = Cli {
+        program,
+        options,
+        stdout,
+        stderr,
+        failed: false,
+    };
+    if cli.options.check {
+        cli.check_all()?;
+    } else {
+        cli.print_all()?;
+    }
+    cli.stdout.flush()?;
+    Ok(i32::from(cli.failed))
+}
+
+/// Text printed by `--help`
+fn usage(program: &str) -> String {
+    let models: Vec<&str> = CATALOGUE.iter().map(|(name, _)| *name).collect();
+    format!(
+        "Usage: {program} [OPTION]... [FILE]...\n\
+         Print or check CRC checksums.\n\
+         With no FILE, or when FILE is -, read standard input.\n\
+         \n\
+         \x20 -c, --check        read checksums from the FILEs and check them\n\
+         \x20 -m, --model NAME   compute the NAME model: crc32, crc32c, {}\n\
+         \x20     --hex          print and read checksums in hexadecimal (default)\n\
+         \x20     --dec          print and read checksums in decimal\n\
+         \x20 -r, --recursive    hash the files of directories recursively,\n\
+         \x20                    without following symbolic links to directories\n\
+         \x20 -q, --quiet        do not print OK for each successfully verified file\n\
+         \x20 -h, --help         display this help and exit\n",
+        models.join(", ")
+    )
+}
+
+/// State of a run of the binary.
+/// The methods return the errors of the outputs, the errors of the inputs are reported and mark the
+/// run as failed.
+struct Cli<'a> {
+    program: &'a str,
+    options: Options,
+    stdout: &'a mut dyn Write,
+    stderr: &'a mut dyn Write,
+    failed: bool,
+}
+
+impl Cli<'_> {
+    /// Reports an error on a path and marks the run as failed
+    fn error(&mut self, path: &Path, error: impl std::fmt::Display) -> io::Result<()> {
+        self.failed = true;
+        writeln!(self.stderr, "{}: {}: {error}", self.program, path.display())
+    }
+
+    /// Prints the checksum of every file
+    fn print_all(&mut self) -> io::Result<()> {
+        for path in std::mem::take(&mut self.options.files) {
+            self.print(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Prints the checksum of the file, or of each file of the directory when recursive
+    fn print(&mut self, path: &Path) -> io::Result<()> {
+        if path.is_dir() {
+            if !self.options.recursive {
+                return self.error(path, "Is a directory");
+            }
+            return self.print_dir(path);
+        }
+        match open_input(path, |reader| self.options.model.checksum(reader)) {
+            Ok(crc) => {
+                let crc = format_checksum(crc, self.options.decimal);
+                writeln!(self.stdout, "{crc}  {}", path.display())
+            }
+            Err(error) => self.error(path, error),
+        }
+    }
+
+    /// Prints the checksum of each file of the directory.
+    /// Symbolic links to directories are skipped, so that a cycle of links is not walked forever.
+    fn print_dir(&mut self, dir: &Path) -> io::Result<()> {
+        let entries =
+            match std::fs::read_dir(dir).and_then(|dir| dir.collect::<io::Result<Vec<_>>>()) {
+                Ok(entries) => entries,
+                Err(error) => return self.error(dir, error),
+            };
+        let mut paths = Vec::new();
+        for entry in entries {
+            let path = entry.path();
+            let is_symlink = std::fs::symlink_metadata(&path)
+                .map(|metadata| metadata.file_type().is_symlink())
+                .unwrap_or(false);
+            if !(is_symlink && path.is_dir()) {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        for path in paths {
+            self.print(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Verifies the checksums listed in every file
+    fn check_all(&mut self) -> io::Result<()> {
+        for path in std::mem::take(&mut self.options.files) {
+            match open_input(&path, |reader| {
+                Ok(self.check(&path, &mut BufReader::new(reader)))
+            }) {
+                Ok(written) => written?,
+                Err(error) => self.error(&path, error)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies every checksum of the list and prints a summary of the failures
+    fn check(&mut self, list_path: &Path, list: &mut dyn BufRead) -> io::Result<()> {
+        let (mut malformed, mut unreadable, mut mismatched) = (0, 0, 0);
+        for line in list.lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(error) => {
+                    self.error(list_path, error)?;
+                    break;
+                }
+            };
+            // Lists written on Windows end their lines with CRLF
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+            let Some((expected, path)) = parse_check_line(line, self.options.decimal) else {
+                malformed += 1;
+                continue;
+            };
+            match open_input(Path::new(path), |reader| {
+                self.options.model.checksum(reader)
+            }) {
+                Ok(crc) if crc == expected => {
+                    if !self.options.quiet {
+                        writeln!(self.stdout, "{path}: OK")?;
+                    }
+                }
+                Ok(_) => {
+                    mismatched += 1;
+                    writeln!(self.stdout, "{path}: FAILED")?;
+                }
+                Err(error) => {
+                    unreadable += 1;
+                    writeln!(self.stderr, "{}: {path}: {error}", self.program)?;
+                    writeln!(self.stdout, "{path}: FAILED open or read")?;
+                }
+            }
+        }
+        for (count, warning) in [
+            (malformed, "line is improperly formatted"),
+            (unreadable, "listed file could not be read"),
+            (mismatched, "computed checksum did NOT match"),
+        ] {
+            if count > 0 {
+                self.failed = true;
+                writeln!(self.stderr, "{}: WARNING: {count} {warning}", self.program)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_checksum, parse_check_line, run, Model, CATALOGUE};
+    use hud_slice_by_8::{crc32, crc32c, CHECK_INPUT};
+    use std::ffi::OsString;
+    use std::io::{self, Write};
+
+    const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog, 0123456789";
+
+    /// Runs the binary and returns its exit code, standard output and standard error
+    fn run_args(args: &[&str]) -> (i32, String, String) {
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let args = args.iter().map(OsString::from);
+        let code = run("crc32sum", Model::Crc32, args, &mut stdout, &mut stderr);
+        (
+            code,
+            String::from_utf8(stdout).unwrap(),
+            String::from_utf8(stderr).unwrap(),
+        )
+    }
+
+    #[test]
+    fn checksum_is_coherent_with_slice_by_8() {
+        let buf: Vec<u8> = (0..300_000u32).map(|i| (i * 31) as u8).collect();
+        let checksum = |model: Model| model.checksum(&mut &buf[..]).unwrap();
+        assert_eq!(checksum(Model::Crc32), crc32::slice_by_8(&buf));
+        assert_eq!(checksum(Model::Crc32c), crc32c::slice_by_8(&buf));
+        assert_eq!(
+            checksum(Model::from_name("BZIP2").unwrap()),
+            crc32::bzip2::slice_by_8(&buf)
+        );
+    }
+
+    #[test]
+    fn catalogued_models_match_their_check_value() {
+        for (name, params) in CATALOGUE {
+            let model = Model::from_name(name).unwrap();
+            assert_eq!(model.checksum(&mut &CHECK_INPUT[..]).unwrap(), params.check);
+        }
+        assert!(Model::from_name("crc64").is_none());
+    }
+
+    #[test]
+    fn check_lines_are_parsed() {
+        assert_eq!(
+            parse_check_line("cbf43926  file", false),
+            Some((0xCBF43926, "file"))
+        );
+        assert_eq!(
+            parse_check_line("cbf43926 *a file", false),
+            Some((0xCBF43926, "a file"))
+        );
+        assert_eq!(
+            parse_check_line("3421780262  file", true),
+            Some((0xCBF43926, "file"))
+        );
+        assert_eq!(parse_check_line("cbf43926 file", false), None);
+        assert_eq!(parse_check_line("cbf4392  file", false), None);
+        assert_eq!(parse_check_line("cbf43926  ", false), None);
+        assert_eq!(format_checksum(0x0000ABCD, false), "0000abcd");
+        assert_eq!(format_checksum(0x0000ABCD, true), "43981");
+    }
+
+    #[test]
+    fn files_are_printed_and_checked() {
+        let name = format!(
+            "hud_slice_by_8_{}_files_are_printed_and_checked",
+            std::process::id()
+        );
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        let (a, b) = (dir.join("a"), dir.join("nested").join("b"));
+        std::fs::write(&a, HASH_ME).unwrap();
+        std::fs::write(&b, CHECK_INPUT).unwrap();
+        let dir_arg = dir.to_str().unwrap();
+
+        let (code, _, stderr) = run_args(&[dir_arg]);
+        assert_eq!(code, 1);
+        assert!(stderr.contains("Is a directory"));
+
+        let (code, stdout, _) = run_args(&["-r", dir_arg]);
+        assert_eq!(code, 0);
+        let expected = format!(
+            "{:08x}  {}\ncbf43926  {}\n",
+            crc32::slice_by_8(HASH_ME),
+            a.display(),
+            b.display()
+        );
+        assert_eq!(stdout, expected);
+
+        let list = dir.join("list");
+        std::fs::write(&list, &stdout).unwrap();
+        let (code, stdout, _) = run_args(&["--check", list.to_str().unwrap()]);
+        assert_eq!(code, 0);
+        assert_eq!(
+            stdout,
+            format!("{}: OK\n{}: OK\n", a.display(), b.display())
+        );
+
+        let crlf_list = dir.join("crlf_list");
+        let crlf = std::fs::read_to_string(&list).unwrap().replace('\n', "\r\n");
+        std::fs::write(&crlf_list, crlf).unwrap();
+        let (code, _, _) = run_args(&["-cq", crlf_list.to_str().unwrap()]);
+        assert_eq!(code, 0);
+
+        std::fs::write(&b, HASH_ME).unwrap();
+        let (code, stdout, stderr) = run_args(&["-cq", list.to_str().unwrap()]);
+        assert_eq!(code, 1);
+        assert_eq!(stdout, format!("{}: FAILED\n", b.display()));
+        assert!(stderr.contains("1 computed checksum did NOT match"));
+
+        let (code, stdout, _) = run_args(&["--model=crc32c", "--dec", a.to_str().unwrap()]);
+        assert_eq!(code, 0);
+        assert_eq!(
+            stdout,
+            format!("{}  {}\n", crc32c::slice_by_8(HASH_ME), a.display())
+        );
+
+        let (code, stdout, _) = run_args(&["-qmcrc32c", "--dec", a.to_str().unwrap()]);
+        assert_eq!(code, 0);
+        assert_eq!(
+            stdout,
+            format!("{}  {}\n", crc32c::slice_by_8(HASH_ME), a.display())
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn symlinked_directories_are_not_followed() {
+        let name = format!(
+            "hud_slice_by_8_{}_symlinked_directories_are_not_followed",
+            std::process::id()
+        );
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("file");
+        std::fs::write(&file, CHECK_INPUT).unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("cycle")).unwrap();
+        std::os::unix::fs::symlink(&file, dir.join("link")).unwrap();
+
+        let (code, stdout, _) = run_args(&["-r", dir.to_str().unwrap()]);
+        assert_eq!(code, 0);
+        assert_eq!(
+            stdout,
+            format!(
+                "cbf43926  {}\ncbf43926  {}\n",
+                file.display(),
+                dir.join("link").display()
+            )
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn write_errors_are_reported() {
+        /// Output of a closed pipe
+        struct ClosedPipe;
+
+        impl Write for ClosedPipe {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        for args in [&["-"][..], &["--help"]] {
+            let mut stderr = Vec::new();
+            let args = args.iter().map(OsString::from);
+            let code = run("crc32sum", Model::Crc32, args, &mut ClosedPipe, &mut stderr);
+            assert_eq!(code, 1);
+            let stderr = String::from_utf8(stderr).unwrap();
+            assert!(stderr.starts_with("crc32sum: write error: "));
+        }
+    }
+
+    #[test]
+    fn invalid_arguments_are_reported() {
+        let (code, _, stderr) = run_args(&["--model", "unknown"]);
+        assert_eq!(code, 1);
+        assert!(stderr.contains("unknown model 'unknown'"));
+        let (code, _, stderr) = run_args(&["--frobnicate"]);
+        assert_eq!(code, 1);
+        assert!(stderr.contains("unrecognized option '--frobnicate'"));
+        let (code, stdout, _) = run_args(&["--help"]);
+        assert_eq!(code, 0);
+        assert!(stdout.starts_with("Usage: crc32sum"));
+    }
+}
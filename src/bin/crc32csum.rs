@@ -0,0 +1,14 @@
+//!
+//! Prints or checks CRC-32C checksums of files and standard input in the `sha256sum` format.
+mod cli;
+
+fn main() {
+    let code = cli::run(
+        "crc32csum",
+        cli::Model::Crc32c,
+        std::env::args_os().skip(1),
+        &mut std::io::stdout().lock(),
+        &mut std::io::stderr().lock(),
+    );
+    std::process::exit(code);
+}
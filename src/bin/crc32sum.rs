@@ -0,0 +1,14 @@
+//!
+//! Prints or checks CRC-32 checksums of files and standard input in the `sha256sum` format.
+mod cli;
+
+fn main() {
+    let code = cli::run(
+        "crc32sum",
+        cli::Model::Crc32,
+        std::env::args_os().skip(1),
+        &mut std::io::stdout().lock(),
+        &mut std::io::stderr().lock(),
+    );
+    std::process::exit(code);
+}
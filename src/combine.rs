@@ -0,0 +1,146 @@
+//!
+//! Combination of the CRC of two adjacent blocks without hashing them again.
+//! Adapation from zlib `crc32_combine` by Mark Adler:
+//! the CRC of the first block is multiplied by `x^(8 * len_b)` modulo the polynomial.
+use crate::CrcWidth;
+
+/// Computes the CRC of the concatenation of two blocks from the CRC of each block.
+/// Both CRC are computed by [slice_by_8](crate::slice_by_8) with the reflected lookup table
+/// generated with the same polynomial.
+///
+/// `crc_a` may be seeded, the result is then the CRC of the concatenation with the same seed.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{combine, crc32, generate_table, slice_by_8};
+///
+/// const LOOKUP_TABLE: [[u32; 256]; 8] = generate_table(crc32::POLYNOMIAL);
+/// let crc_a = slice_by_8(b"abcdefghijklm", &LOOKUP_TABLE);
+/// let crc_b = slice_by_8(b"nopqrstuvwxyz", &LOOKUP_TABLE);
+///
+/// assert_eq!(combine(crc_a, crc_b, 13, crc32::POLYNOMIAL), 0x4C2750BD);
+/// ```
+pub fn combine<W: CrcWidth>(crc_a: W, crc_b: W, len_b: u64, polynomial: W) -> W {
+    shift(crc_a, len_b, polynomial) ^ crc_b
+}
+
+/// Multiplies the reflected `crc` by `x^(8 * len)` modulo the polynomial.
+/// This is the effect on the register of `len` zero bytes.
+pub(crate) fn shift<W: CrcWidth>(crc: W, len: u64, polynomial: W) -> W {
+    let reversed_polynomial = polynomial.reverse_bits().to_u128();
+    let x_8n = x_pow_8n(len, reversed_polynomial, W::BITS);
+    W::from_u128(multiply(crc.to_u128(), x_8n, reversed_polynomial, W::BITS))
+}
+
+/// Computes `x^(8 * len)` modulo the reflected polynomial of `width` bits.
+/// The arithmetic is `const` on `u128` so shift tables can be generated at compile time.
+pub(crate) const fn x_pow_8n(len: u64, reversed_polynomial: u128, width: u32) -> u128 {
+    // In the reflected representation x^0 is the most significant bit
+    let x_0 = 1 << (width - 1);
+    let mut x_8 = x_0 >> 1;
+    let mut i = 0;
+    while i < 3 {
+        x_8 = multiply(x_8, x_8, reversed_polynomial, width);
+        i += 1;
+    }
+
+    // Exponentiation by squaring of x^8
+    let (mut x_8n, mut square, mut len) = (x_0, x_8, len);
+    while len != 0 {
+        if len & 1 == 1 {
+            x_8n = multiply(x_8n, square, reversed_polynomial, width);
+        }
+        square = multiply(square, square, reversed_polynomial, width);
+        len >>= 1;
+    }
+    x_8n
+}
+
+/// Multiplies the reflected polynomials `a` and `b` of `width` bits modulo the reflected polynomial.
+pub(crate) const fn multiply(mut a: u128, mut b: u128, reversed_polynomial: u128, width: u32) -> u128 {
+    let x_0 = 1 << (width - 1);
+    let mask = u128::MAX >> (128 - width);
+    let mut product = 0;
+    while a != 0 {
+        // Most significant bit of a is the lowest power of x
+        if a & x_0 != 0 {
+            product ^= b;
+        }
+        a = (a << 1) & mask;
+        b = if b & 1 == 1 {
+            (b >> 1) ^ reversed_polynomial
+        } else {
+            b >> 1
+        };
+    }
+    product
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        combine, crc64, generate_table, generate_table_u128, generate_table_u16, generate_table_u8,
+        slice_by_8, slice_by_8_with_seed,
+    };
+
+    const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog, 0123456789";
+
+    #[test]
+    fn combine_is_coherent_with_slice_by_8() {
+        const LOOKUP_TABLE: [[u32; 256]; 8] = generate_table(0x1EDC6F41);
+        for split in 0..=HASH_ME.len() {
+            let (a, b) = HASH_ME.split_at(split);
+            let crc_a = slice_by_8(a, &LOOKUP_TABLE);
+            let crc_b = slice_by_8(b, &LOOKUP_TABLE);
+            assert_eq!(
+                combine(crc_a, crc_b, b.len() as u64, 0x1EDC6F41),
+                slice_by_8(HASH_ME, &LOOKUP_TABLE)
+            );
+        }
+    }
+
+    #[test]
+    fn combine_keeps_the_seed_of_the_first_block() {
+        const LOOKUP_TABLE: [[u32; 256]; 8] = generate_table(0x04C11DB7);
+        let (a, b) = HASH_ME.split_at(20);
+        let crc_a = slice_by_8_with_seed(a, 123456789, &LOOKUP_TABLE);
+        let crc_b = slice_by_8(b, &LOOKUP_TABLE);
+        assert_eq!(
+            combine(crc_a, crc_b, b.len() as u64, 0x04C11DB7),
+            slice_by_8_with_seed(HASH_ME, 123456789, &LOOKUP_TABLE)
+        );
+    }
+
+    #[test]
+    fn combine_every_width() {
+        let (a, b) = HASH_ME.split_at(17);
+
+        const LOOKUP_TABLE_16: [[u16; 256]; 8] = generate_table_u16(0x8005);
+        let (crc_a, crc_b) = (slice_by_8(a, &LOOKUP_TABLE_16), slice_by_8(b, &LOOKUP_TABLE_16));
+        assert_eq!(
+            combine(crc_a, crc_b, b.len() as u64, 0x8005),
+            slice_by_8(HASH_ME, &LOOKUP_TABLE_16)
+        );
+
+        const LOOKUP_TABLE_8: [[u8; 256]; 8] = generate_table_u8(0x07);
+        let (crc_a, crc_b) = (slice_by_8(a, &LOOKUP_TABLE_8), slice_by_8(b, &LOOKUP_TABLE_8));
+        assert_eq!(
+            combine(crc_a, crc_b, b.len() as u64, 0x07),
+            slice_by_8(HASH_ME, &LOOKUP_TABLE_8)
+        );
+
+        const POLYNOMIAL_128: u128 = 0x0000000000000000000000000000008B;
+        let lookup_table_128 = generate_table_u128(POLYNOMIAL_128);
+        let (crc_a, crc_b) = (slice_by_8(a, &lookup_table_128), slice_by_8(b, &lookup_table_128));
+        assert_eq!(
+            combine(crc_a, crc_b, b.len() as u64, POLYNOMIAL_128),
+            slice_by_8(HASH_ME, &lookup_table_128)
+        );
+
+        let (crc_a, crc_b) = (crc64::slice_by_8(a), crc64::slice_by_8(b));
+        assert_eq!(
+            combine(crc_a, crc_b, b.len() as u64, crc64::POLYNOMIAL),
+            crc64::slice_by_8(HASH_ME)
+        );
+    }
+}
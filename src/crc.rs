@@ -0,0 +1,544 @@
+//!
+//! Generic CRC engine following the Rocksoft model described in
+//! "A Painless Guide to CRC Error Detection Algorithms" by Ross N. Williams.
+//! Parameters of known models are listed in the
+//! [CRC RevEng catalogue](https://reveng.sourceforge.io/crc-catalogue/).
+use crate::algorithm;
+use crate::combine;
+use crate::{
+    generate_normal_table, generate_normal_table_u128, generate_normal_table_u16,
+    generate_normal_table_u64, generate_normal_table_u8, generate_table, generate_table_u128,
+    generate_table_u16, generate_table_u64, generate_table_u8, CrcWidth,
+};
+
+/// Data used to compute the check value of a [GenericCrcParams]
+pub const CHECK_INPUT: &[u8] = b"123456789";
+
+/// Parameters of a CRC following the Rocksoft model
+///
+/// The width of the CRC is the number of bits of the register `W`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GenericCrcParams<W: CrcWidth> {
+    /// Polynomial in normal (MSB-first) notation
+    pub poly: W,
+    /// Initial value of the register, in normal (MSB-first) notation
+    pub init: W,
+    /// Whether each input byte is reflected before being processed
+    pub refin: bool,
+    /// Whether the register is reflected before the final XOR
+    pub refout: bool,
+    /// Value XORed with the register to produce the checksum
+    pub xorout: W,
+    /// Checksum of [CHECK_INPUT] (`b"123456789"`)
+    pub check: W,
+}
+
+/// CRC engine computing checksums of a [GenericCrcParams] with the slicing by 8 algorithm
+///
+/// The engine verifies the parameters against their [GenericCrcParams::check] value when created.
+#[derive(Debug, Clone)]
+pub struct GenericCrc<W: CrcWidth> {
+    params: GenericCrcParams<W>,
+    lookup_table: [[W; 256]; 8],
+    /// Constants of the `pmull` folding, derived once like the lookup table
+    #[cfg(all(target_arch = "aarch64", any(target_feature = "aes", feature = "std")))]
+    fold_keys: crate::fold::FoldKeys,
+}
+
+impl<W: CrcWidth> GenericCrc<W> {
+    /// Parameters of the engine
+    pub const fn params(&self) -> &GenericCrcParams<W> {
+        &self.params
+    }
+
+    /// Computes the checksum for the specified buffer
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::{crc32, Crc};
+    ///
+    /// const CRC_32: Crc = Crc::new(crc32::PARAMS);
+    /// const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+    ///
+    /// assert_eq!(CRC_32.checksum(HASH_ME), crc32::slice_by_8(HASH_ME));
+    /// ```
+    pub fn checksum(&self, buf: &[u8]) -> W {
+        self.finalize(self.update(self.init(), buf))
+    }
+
+    /// Lookup table of the engine.
+    /// The table is reflected if [GenericCrcParams::refin] is set.
+    pub const fn lookup_table(&self) -> &[[W; 256]; 8] {
+        &self.lookup_table
+    }
+
+    /// Computes the checksum for the specified buffer, resuming from `seed`,
+    /// the checksum of the previous data
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::{crc32, Crc};
+    ///
+    /// const CRC_32_BZIP2: Crc = Crc::new(crc32::bzip2::PARAMS);
+    /// let crc = CRC_32_BZIP2.checksum(b"1234");
+    ///
+    /// assert_eq!(CRC_32_BZIP2.checksum_with_seed(b"56789", crc), 0xFC891918);
+    /// ```
+    pub fn checksum_with_seed(&self, buf: &[u8], seed: W) -> W {
+        self.finalize(self.update(self.resume(seed), buf))
+    }
+
+    /// Computes the checksum of the concatenation of two blocks from the checksum of each block
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::{crc32, Crc};
+    ///
+    /// const CRC_32_BZIP2: Crc = Crc::new(crc32::bzip2::PARAMS);
+    /// let crc_a = CRC_32_BZIP2.checksum(b"1234");
+    /// let crc_b = CRC_32_BZIP2.checksum(b"56789");
+    ///
+    /// assert_eq!(CRC_32_BZIP2.combine(crc_a, crc_b, 5), 0xFC891918);
+    /// ```
+    pub fn combine(&self, crc_a: W, crc_b: W, len_b: u64) -> W {
+        // Work on reflected registers whatever the parameters are
+        let reflected = |crc: W| {
+            if self.params.refout {
+                crc
+            } else {
+                crc.reverse_bits()
+            }
+        };
+        let register_a = reflected(crc_a ^ self.params.xorout) ^ self.params.init.reverse_bits();
+        let register_b = reflected(crc_b ^ self.params.xorout);
+        let register = combine::shift(register_a, len_b, self.params.poly) ^ register_b;
+        reflected(register) ^ self.params.xorout
+    }
+
+    /// Initial value of the register to stream data with [GenericCrc::update].
+    /// The register is reflected when [GenericCrcParams::refin] is set.
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::{crc32, Crc};
+    ///
+    /// const CRC_32_BZIP2: Crc = Crc::new(crc32::bzip2::PARAMS);
+    ///
+    /// let mut register = CRC_32_BZIP2.init();
+    /// register = CRC_32_BZIP2.update(register, b"1234");
+    /// register = CRC_32_BZIP2.update(register, b"56789");
+    ///
+    /// assert_eq!(CRC_32_BZIP2.finalize(register), 0xFC891918);
+    /// ```
+    #[inline(always)]
+    pub fn init(&self) -> W {
+        if self.params.refin {
+            self.params.init.reverse_bits()
+        } else {
+            self.params.init
+        }
+    }
+
+    /// Updates the register with the specified buffer
+    #[inline(always)]
+    pub fn update(&self, crc: W, buf: &[u8]) -> W {
+        if self.params.refin {
+            #[cfg(all(target_arch = "aarch64", any(target_feature = "aes", feature = "std")))]
+            if W::BITS <= 64 && crate::fold::aarch64::is_supported() {
+                // SAFETY: pmull is supported
+                return unsafe {
+                    crate::fold::aarch64::update(crc, buf, &self.fold_keys, &self.lookup_table)
+                };
+            }
+            algorithm::update(crc, buf, &self.lookup_table)
+        } else {
+            algorithm::update_normal(crc, buf, &self.lookup_table)
+        }
+    }
+
+    /// Restores the register from a checksum, the inverse of [GenericCrc::finalize]
+    #[inline(always)]
+    pub(crate) fn resume(&self, crc: W) -> W {
+        if self.params.refin == self.params.refout {
+            crc ^ self.params.xorout
+        } else {
+            (crc ^ self.params.xorout).reverse_bits()
+        }
+    }
+
+    /// Produces the checksum from the register
+    #[inline(always)]
+    pub fn finalize(&self, crc: W) -> W {
+        // The register is reflected on output if its orientation differs from refout
+        if self.params.refin == self.params.refout {
+            crc ^ self.params.xorout
+        } else {
+            crc.reverse_bits() ^ self.params.xorout
+        }
+    }
+}
+
+/// Implement the `const` constructor of a [GenericCrc] for a register type.
+/// Lookup tables are generated by `const fn` that can not be generic over [CrcWidth].
+macro_rules! impl_const_new {
+    ($ty:ty, $params:ident, $generate_table:ident, $generate_normal_table:ident) => {
+        impl GenericCrc<$ty> {
+            #[doc = concat!("Create a new engine for the given [", stringify!($params), "]")]
+            ///
+            /// # Panics
+            /// Panics if the checksum of [CHECK_INPUT] is not [GenericCrcParams::check].
+            /// In a constant expression this is a compile time error.
+            pub const fn new(params: $params) -> GenericCrc<$ty> {
+                let lookup_table = if params.refin {
+                    $generate_table(params.poly)
+                } else {
+                    $generate_normal_table(params.poly)
+                };
+
+                // Verify the model against its check value
+                let mut crc = if params.refin {
+                    params.init.reverse_bits()
+                } else {
+                    params.init
+                };
+                let mut i = 0;
+                while i < CHECK_INPUT.len() {
+                    let byte = CHECK_INPUT[i];
+                    // 8 bit registers are entirely shifted out by each byte
+                    crc = if params.refin {
+                        let shifted = match crc.checked_shr(8) {
+                            Some(shifted) => shifted,
+                            None => 0,
+                        };
+                        lookup_table[0][((crc & 0xFF) as u8 ^ byte) as usize] ^ shifted
+                    } else {
+                        let shifted = match crc.checked_shl(8) {
+                            Some(shifted) => shifted,
+                            None => 0,
+                        };
+                        lookup_table[0][((crc >> (<$ty>::BITS - 8)) as u8 ^ byte) as usize]
+                            ^ shifted
+                    };
+                    i += 1;
+                }
+                let check = if params.refin == params.refout {
+                    crc
+                } else {
+                    crc.reverse_bits()
+                } ^ params.xorout;
+                assert!(
+                    check == params.check,
+                    "CRC parameters do not match their check value"
+                );
+
+                GenericCrc {
+                    params,
+                    lookup_table,
+                    // Registers wider than 64 bits are not folded
+                    #[cfg(all(
+                        target_arch = "aarch64",
+                        any(target_feature = "aes", feature = "std")
+                    ))]
+                    fold_keys: if <$ty>::BITS <= 64 {
+                        crate::fold::FoldKeys::new(params.poly as u64, <$ty>::BITS)
+                    } else {
+                        crate::fold::FoldKeys::UNUSED
+                    },
+                }
+            }
+        }
+    };
+}
+
+impl_const_new!(u8, Crc8Params, generate_table_u8, generate_normal_table_u8);
+impl_const_new!(u16, Crc16Params, generate_table_u16, generate_normal_table_u16);
+impl_const_new!(u32, CrcParams, generate_table, generate_normal_table);
+impl_const_new!(u64, Crc64Params, generate_table_u64, generate_normal_table_u64);
+impl_const_new!(u128, Crc128Params, generate_table_u128, generate_normal_table_u128);
+
+/// Parameters of a 32 bit CRC following the Rocksoft model
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::CrcParams;
+///
+/// // CRC-32/BZIP2
+/// const CRC_32_BZIP2: CrcParams = CrcParams {
+///     poly: 0x04C11DB7,
+///     init: 0xFFFFFFFF,
+///     refin: false,
+///     refout: false,
+///     xorout: 0xFFFFFFFF,
+///     check: 0xFC891918,
+/// };
+/// ```
+pub type CrcParams = GenericCrcParams<u32>;
+
+/// CRC engine computing checksums of a [CrcParams] with the slicing by 8 algorithm
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{Crc, CrcParams};
+///
+/// const CRC_32_MPEG_2: Crc = Crc::new(CrcParams {
+///     poly: 0x04C11DB7,
+///     init: 0xFFFFFFFF,
+///     refin: false,
+///     refout: false,
+///     xorout: 0x00000000,
+///     check: 0x0376E6E7,
+/// });
+///
+/// assert_eq!(CRC_32_MPEG_2.checksum(b"123456789"), 0x0376E6E7);
+/// ```
+pub type Crc = GenericCrc<u32>;
+
+/// Parameters of a 8 bit CRC following the Rocksoft model
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::Crc8Params;
+///
+/// // CRC-8/I-CODE
+/// const CRC_8_I_CODE: Crc8Params = Crc8Params {
+///     poly: 0x1D,
+///     init: 0xFD,
+///     refin: false,
+///     refout: false,
+///     xorout: 0x00,
+///     check: 0x7E,
+/// };
+/// ```
+pub type Crc8Params = GenericCrcParams<u8>;
+
+/// CRC engine computing checksums of a [Crc8Params] with the slicing by 8 algorithm
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc8, Crc8};
+///
+/// const CRC_8_SMBUS: Crc8 = Crc8::new(crc8::smbus::PARAMS);
+///
+/// assert_eq!(CRC_8_SMBUS.checksum(b"123456789"), 0xF4);
+/// ```
+pub type Crc8 = GenericCrc<u8>;
+
+/// Parameters of a 16 bit CRC following the Rocksoft model
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::Crc16Params;
+///
+/// // CRC-16/GENIBUS
+/// const CRC_16_GENIBUS: Crc16Params = Crc16Params {
+///     poly: 0x1021,
+///     init: 0xFFFF,
+///     refin: false,
+///     refout: false,
+///     xorout: 0xFFFF,
+///     check: 0xD64E,
+/// };
+/// ```
+pub type Crc16Params = GenericCrcParams<u16>;
+
+/// CRC engine computing checksums of a [Crc16Params] with the slicing by 8 algorithm
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc16, Crc16};
+///
+/// const CRC_16_XMODEM: Crc16 = Crc16::new(crc16::xmodem::PARAMS);
+///
+/// assert_eq!(CRC_16_XMODEM.checksum(b"123456789"), 0x31C3);
+/// ```
+pub type Crc16 = GenericCrc<u16>;
+
+/// Parameters of a 64 bit CRC following the Rocksoft model
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::Crc64Params;
+///
+/// // CRC-64/WE
+/// const CRC_64_WE: Crc64Params = Crc64Params {
+///     poly: 0x42F0E1EBA9EA3693,
+///     init: 0xFFFFFFFFFFFFFFFF,
+///     refin: false,
+///     refout: false,
+///     xorout: 0xFFFFFFFFFFFFFFFF,
+///     check: 0x62EC59E3F1A4F00A,
+/// };
+/// ```
+pub type Crc64Params = GenericCrcParams<u64>;
+
+/// CRC engine computing checksums of a [Crc64Params] with the slicing by 8 algorithm
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc64, Crc64};
+///
+/// const CRC_64_ECMA_182: Crc64 = Crc64::new(crc64::ECMA_182);
+///
+/// assert_eq!(CRC_64_ECMA_182.checksum(b"123456789"), 0x6C40DF5F0B497347);
+/// ```
+pub type Crc64 = GenericCrc<u64>;
+
+/// Parameters of a 128 bit CRC following the Rocksoft model
+pub type Crc128Params = GenericCrcParams<u128>;
+
+/// CRC engine computing checksums of a [Crc128Params] with the slicing by 8 algorithm
+pub type Crc128 = GenericCrc<u128>;
+
+#[cfg(test)]
+mod tests {
+    use super::{Crc, Crc16, Crc64, Crc8, CrcParams, CHECK_INPUT};
+    use crate::{algorithm, crc16, crc32, crc32c, crc64, crc8};
+
+    const CRC_32_BZIP2: CrcParams = CrcParams {
+        poly: 0x04C11DB7,
+        init: 0xFFFFFFFF,
+        refin: false,
+        refout: false,
+        xorout: 0xFFFFFFFF,
+        check: 0xFC891918,
+    };
+
+    const CRC_32_POSIX: CrcParams = CrcParams {
+        poly: 0x04C11DB7,
+        init: 0x00000000,
+        refin: false,
+        refout: false,
+        xorout: 0xFFFFFFFF,
+        check: 0x765E7680,
+    };
+
+    const CRC_32_MPEG_2: CrcParams = CrcParams {
+        poly: 0x04C11DB7,
+        init: 0xFFFFFFFF,
+        refin: false,
+        refout: false,
+        xorout: 0x00000000,
+        check: 0x0376E6E7,
+    };
+
+    const CRC_32_JAMCRC: CrcParams = CrcParams {
+        poly: 0x04C11DB7,
+        init: 0xFFFFFFFF,
+        refin: true,
+        refout: true,
+        xorout: 0x00000000,
+        check: 0x340BC6D9,
+    };
+
+    #[test]
+    fn models_match_their_check_value() {
+        for params in [
+            crc32::PARAMS,
+            crc32c::PARAMS,
+            CRC_32_BZIP2,
+            CRC_32_POSIX,
+            CRC_32_MPEG_2,
+            CRC_32_JAMCRC,
+        ] {
+            assert_eq!(Crc::new(params).checksum(CHECK_INPUT), params.check);
+        }
+    }
+
+    #[test]
+    fn models_8_match_their_check_value() {
+        for params in [
+            crc8::autosar::PARAMS,
+            crc8::bluetooth::PARAMS,
+            crc8::maxim_dow::PARAMS,
+            crc8::smbus::PARAMS,
+        ] {
+            assert_eq!(Crc8::new(params).checksum(CHECK_INPUT), params.check);
+        }
+    }
+
+    #[test]
+    fn models_16_match_their_check_value() {
+        for params in [
+            crc16::arc::PARAMS,
+            crc16::ccitt_false::PARAMS,
+            crc16::kermit::PARAMS,
+            crc16::modbus::PARAMS,
+            crc16::xmodem::PARAMS,
+        ] {
+            assert_eq!(Crc16::new(params).checksum(CHECK_INPUT), params.check);
+        }
+    }
+
+    #[test]
+    fn models_64_match_their_check_value() {
+        for params in [crc64::PARAMS, crc64::ECMA_182, crc64::NVME] {
+            assert_eq!(Crc64::new(params).checksum(CHECK_INPUT), params.check);
+        }
+    }
+
+    #[test]
+    fn checksum_is_coherent_with_slice_by_8() {
+        const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+        assert_eq!(Crc::new(crc32::PARAMS).checksum(HASH_ME), 0x4C2750BD);
+        assert_eq!(Crc::new(crc32c::PARAMS).checksum(HASH_ME), 0x9EE6EF25);
+        assert_eq!(Crc64::new(crc64::PARAMS).checksum(HASH_ME), crc64::slice_by_8(HASH_ME));
+    }
+
+    #[test]
+    fn checksum_of_large_buffer_is_coherent_with_slice_by_8() {
+        let mut buf = [0u8; 4096];
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = (i * 31) as u8;
+        }
+        for len in [1024, 1025, 2000, 4096] {
+            let buf = &buf[..len];
+            assert_eq!(Crc::new(crc32::PARAMS).checksum(buf), crc32::slice_by_8(buf));
+            assert_eq!(Crc64::new(crc64::PARAMS).checksum(buf), crc64::slice_by_8(buf));
+            let lookup_table = crate::generate_table_u16(crc16::arc::POLYNOMIAL);
+            assert_eq!(Crc16::new(crc16::arc::PARAMS).checksum(buf), algorithm::update(0, buf, &lookup_table));
+            let lookup_table = crate::generate_table_u8(crc8::maxim_dow::POLYNOMIAL);
+            assert_eq!(Crc8::new(crc8::maxim_dow::PARAMS).checksum(buf), algorithm::update(0, buf, &lookup_table));
+        }
+    }
+
+    #[test]
+    fn non_reflected_checksum_is_coherent_with_slice_by_8_normal() {
+        const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+        let lookup_table = crate::generate_normal_table(CRC_32_BZIP2.poly);
+        assert_eq!(
+            Crc::new(CRC_32_BZIP2).checksum(HASH_ME),
+            crate::slice_by_8_normal(HASH_ME, &lookup_table)
+        );
+    }
+
+    #[test]
+    fn combine_is_coherent_with_checksum() {
+        const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog";
+        let (a, b) = HASH_ME.split_at(10);
+        for crc in [
+            Crc::new(crc32::PARAMS),
+            Crc::new(CRC_32_BZIP2),
+            Crc::new(CRC_32_POSIX),
+            Crc::new(CRC_32_JAMCRC),
+        ] {
+            assert_eq!(
+                crc.combine(crc.checksum(a), crc.checksum(b), b.len() as u64),
+                crc.checksum(HASH_ME)
+            );
+        }
+        let crc = Crc16::new(crc16::ccitt_false::PARAMS);
+        assert_eq!(
+            crc.combine(crc.checksum(a), crc.checksum(b), b.len() as u64),
+            crc.checksum(HASH_ME)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "CRC parameters do not match their check value")]
+    fn new_panics_on_wrong_check_value() {
+        Crc::new(CrcParams {
+            check: 0,
+            ..CRC_32_BZIP2
+        });
+    }
+}
@@ -0,0 +1,114 @@
+//! CRC-16 models computed with the slicing by 8 algorithm.
+//!
+//! Every model provides its [Crc16Params](crate::Crc16Params), a lookup table generated at compile
+//! time and a `slice_by_8` function. Parameters are verified against their check value at
+//! compile time.
+//!
+//! # Example
+//! ```
+//! use hud_slice_by_8::crc16;
+//!
+//! assert_eq!(crc16::modbus::slice_by_8(b"123456789"), 0x4B37);
+//! assert_eq!(crc16::xmodem::slice_by_8(b"123456789"), 0x31C3);
+//! ```
+use crate::models::model;
+
+model!(
+    crc16: u16,
+    /// CRC-16/ARC model
+    arc,
+    poly: 0x8005,
+    init: 0x0000,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+    check: 0xBB3D
+);
+
+model!(
+    crc16: u16,
+    /// CRC-16/CCITT-FALSE (also known as CRC-16/IBM-3740) model
+    #[doc(alias = "ibm_3740")]
+    ccitt_false,
+    poly: 0x1021,
+    init: 0xFFFF,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+    check: 0x29B1
+);
+
+model!(
+    crc16: u16,
+    /// CRC-16/KERMIT model
+    kermit,
+    poly: 0x1021,
+    init: 0x0000,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+    check: 0x2189
+);
+
+model!(
+    crc16: u16,
+    /// CRC-16/MODBUS model
+    modbus,
+    poly: 0x8005,
+    init: 0xFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+    check: 0x4B37
+);
+
+model!(
+    crc16: u16,
+    /// CRC-16/XMODEM model
+    xmodem,
+    poly: 0x1021,
+    init: 0x0000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+    check: 0x31C3
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{crc16, generate_normal_table_u16, generate_table_u16};
+
+    #[test]
+    fn slice_by_8_is_coherent_with_reference() {
+        const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(crc16::arc::slice_by_8(HASH_ME), 0xFCDF);
+        assert_eq!(crc16::ccitt_false::slice_by_8(HASH_ME), 0x8FDD);
+        assert_eq!(crc16::kermit::slice_by_8(HASH_ME), 0xC459);
+        assert_eq!(crc16::modbus::slice_by_8(HASH_ME), 0xA89C);
+        assert_eq!(crc16::xmodem::slice_by_8(HASH_ME), 0xF0C8);
+    }
+
+    #[test]
+    fn slice_by_8_with_seed_and_combine_are_coherent_with_slice_by_8() {
+        const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog";
+        for split in [0, 1, 20, HASH_ME.len()] {
+            let (a, b) = HASH_ME.split_at(split);
+            type Model = (fn(&[u8]) -> u16, fn(&[u8], u16) -> u16, fn(u16, u16, u64) -> u16);
+            let models: [Model; 2] = [
+                (crc16::modbus::slice_by_8, crc16::modbus::slice_by_8_with_seed, crc16::modbus::combine),
+                (crc16::xmodem::slice_by_8, crc16::xmodem::slice_by_8_with_seed, crc16::xmodem::combine),
+            ];
+            for (slice_by_8, slice_by_8_with_seed, combine) in models {
+                assert_eq!(slice_by_8_with_seed(b, slice_by_8(a)), slice_by_8(HASH_ME));
+                let (crc_a, crc_b) = (slice_by_8(a), slice_by_8(b));
+                assert_eq!(combine(crc_a, crc_b, b.len() as u64), slice_by_8(HASH_ME));
+            }
+        }
+    }
+
+    #[test]
+    fn lookup_tables_are_correct() {
+        assert_eq!(crc16::kermit::LOOKUP_TABLE, generate_table_u16(0x1021));
+        assert_eq!(crc16::xmodem::LOOKUP_TABLE, generate_normal_table_u16(0x1021));
+    }
+}
@@ -0,0 +1,183 @@
+//! Catalogue of CRC-32 models.
+//!
+//! Every model provides its [CrcParams](crate::CrcParams), a lookup table generated at compile
+//! time and a `slice_by_8` function. Parameters are verified against their check value at
+//! compile time.
+//!
+//! # Example
+//! ```
+//! use hud_slice_by_8::crc32;
+//!
+//! assert_eq!(crc32::bzip2::slice_by_8(b"123456789"), 0xFC891918);
+//! assert_eq!(crc32::cksum::slice_by_8(b"123456789"), 0x765E7680);
+//! ```
+use crate::models::model;
+
+model!(
+    crc32: u32,
+    /// CRC-32/AIXM model
+    aixm,
+    poly: 0x814141AB,
+    init: 0x00000000,
+    refin: false,
+    refout: false,
+    xorout: 0x00000000,
+    check: 0x3010BF7F
+);
+
+model!(
+    crc32: u32,
+    /// CRC-32/AUTOSAR model
+    autosar,
+    poly: 0xF4ACFB13,
+    init: 0xFFFFFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFFFFFF,
+    check: 0x1697D06A
+);
+
+model!(
+    crc32: u32,
+    /// CRC-32/BASE91-D (also known as CRC-32D) model
+    #[doc(alias = "CRC-32D")]
+    base91_d,
+    poly: 0xA833982B,
+    init: 0xFFFFFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFFFFFF,
+    check: 0x87315576
+);
+
+model!(
+    crc32: u32,
+    /// CRC-32/BZIP2 model
+    bzip2,
+    poly: 0x04C11DB7,
+    init: 0xFFFFFFFF,
+    refin: false,
+    refout: false,
+    xorout: 0xFFFFFFFF,
+    check: 0xFC891918
+);
+
+model!(
+    crc32: u32,
+    /// CRC-32/CD-ROM-EDC model
+    cd_rom_edc,
+    poly: 0x8001801B,
+    init: 0x00000000,
+    refin: true,
+    refout: true,
+    xorout: 0x00000000,
+    check: 0x6EC2EDC4
+);
+
+model!(
+    crc32: u32,
+    /// CRC-32/CKSUM (also known as CRC-32/POSIX) model
+    #[doc(alias = "posix")]
+    cksum,
+    poly: 0x04C11DB7,
+    init: 0x00000000,
+    refin: false,
+    refout: false,
+    xorout: 0xFFFFFFFF,
+    check: 0x765E7680
+);
+
+model!(
+    crc32: u32,
+    /// CRC-32/JAMCRC model
+    jamcrc,
+    poly: 0x04C11DB7,
+    init: 0xFFFFFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0x00000000,
+    check: 0x340BC6D9
+);
+
+model!(
+    crc32: u32,
+    /// CRC-32/MPEG-2 model
+    mpeg_2,
+    poly: 0x04C11DB7,
+    init: 0xFFFFFFFF,
+    refin: false,
+    refout: false,
+    xorout: 0x00000000,
+    check: 0x0376E6E7
+);
+
+model!(
+    crc32: u32,
+    /// CRC-32/XFER model
+    xfer,
+    poly: 0x000000AF,
+    init: 0x00000000,
+    refin: false,
+    refout: false,
+    xorout: 0x00000000,
+    check: 0xBD0BE338
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::crc32;
+    use crate::{generate_normal_table, generate_table, CHECK_INPUT};
+
+    #[test]
+    fn models_match_their_check_value() {
+        assert_eq!(crc32::aixm::slice_by_8(CHECK_INPUT), crc32::aixm::PARAMS.check);
+        assert_eq!(crc32::autosar::slice_by_8(CHECK_INPUT), crc32::autosar::PARAMS.check);
+        assert_eq!(crc32::base91_d::slice_by_8(CHECK_INPUT), crc32::base91_d::PARAMS.check);
+        assert_eq!(crc32::bzip2::slice_by_8(CHECK_INPUT), crc32::bzip2::PARAMS.check);
+        assert_eq!(crc32::cd_rom_edc::slice_by_8(CHECK_INPUT), crc32::cd_rom_edc::PARAMS.check);
+        assert_eq!(crc32::cksum::slice_by_8(CHECK_INPUT), crc32::cksum::PARAMS.check);
+        assert_eq!(crc32::jamcrc::slice_by_8(CHECK_INPUT), crc32::jamcrc::PARAMS.check);
+        assert_eq!(crc32::mpeg_2::slice_by_8(CHECK_INPUT), crc32::mpeg_2::PARAMS.check);
+        assert_eq!(crc32::xfer::slice_by_8(CHECK_INPUT), crc32::xfer::PARAMS.check);
+    }
+
+    #[test]
+    fn slice_by_8_is_coherent_with_reference() {
+        const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(crc32::aixm::slice_by_8(HASH_ME), 0xF4965FFC);
+        assert_eq!(crc32::autosar::slice_by_8(HASH_ME), 0x3AAED40A);
+        assert_eq!(crc32::base91_d::slice_by_8(HASH_ME), 0x9D251C62);
+        assert_eq!(crc32::bzip2::slice_by_8(HASH_ME), 0x459DEE61);
+        assert_eq!(crc32::cd_rom_edc::slice_by_8(HASH_ME), 0xD9F91E7E);
+        assert_eq!(crc32::cksum::slice_by_8(HASH_ME), 0x36B78081);
+        assert_eq!(crc32::jamcrc::slice_by_8(HASH_ME), 0xBEB05CC6);
+        assert_eq!(crc32::mpeg_2::slice_by_8(HASH_ME), 0xBA62119E);
+        assert_eq!(crc32::xfer::slice_by_8(HASH_ME), 0x140493E5);
+    }
+
+    #[test]
+    fn slice_by_8_with_seed_and_combine_are_coherent_with_slice_by_8() {
+        const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog";
+        for split in [0, 1, 20, HASH_ME.len()] {
+            let (a, b) = HASH_ME.split_at(split);
+            type Model = (fn(&[u8]) -> u32, fn(&[u8], u32) -> u32, fn(u32, u32, u64) -> u32);
+            let models: [Model; 4] = [
+                (crc32::aixm::slice_by_8, crc32::aixm::slice_by_8_with_seed, crc32::aixm::combine),
+                (crc32::bzip2::slice_by_8, crc32::bzip2::slice_by_8_with_seed, crc32::bzip2::combine),
+                (crc32::cksum::slice_by_8, crc32::cksum::slice_by_8_with_seed, crc32::cksum::combine),
+                (crc32::jamcrc::slice_by_8, crc32::jamcrc::slice_by_8_with_seed, crc32::jamcrc::combine),
+            ];
+            for (slice_by_8, slice_by_8_with_seed, combine) in models {
+                assert_eq!(slice_by_8_with_seed(b, slice_by_8(a)), slice_by_8(HASH_ME));
+                let (crc_a, crc_b) = (slice_by_8(a), slice_by_8(b));
+                assert_eq!(combine(crc_a, crc_b, b.len() as u64), slice_by_8(HASH_ME));
+            }
+        }
+    }
+
+    #[test]
+    fn lookup_tables_are_correct() {
+        assert_eq!(crc32::jamcrc::LOOKUP_TABLE, generate_table(crc32::POLYNOMIAL));
+        assert_eq!(crc32::bzip2::LOOKUP_TABLE, generate_normal_table(crc32::POLYNOMIAL));
+    }
+}
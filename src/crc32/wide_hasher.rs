@@ -0,0 +1,28 @@
+use crate::wide_hasher::{Lanes, WideHasher};
+use core::hash::BuildHasherDefault;
+
+/// Lanes of [CRC32WideHasher]: the CRC32 then the CRC32c checksums
+#[derive(Debug, Default)]
+pub struct CRC32Lanes;
+
+impl Lanes for CRC32Lanes {
+    #[inline(always)]
+    fn key(bytes: &[u8], key: u32) -> u32 {
+        crate::crc32::slice_by_8_with_seed(bytes, key)
+    }
+
+    #[inline(always)]
+    fn other_key(bytes: &[u8], other_key: u32) -> u32 {
+        crate::crc32c::slice_by_8_with_seed(bytes, other_key)
+    }
+}
+
+/// Slice-By-8 hasher with a 64 bit output
+///
+/// The hash has two lanes: the CRC32 and the CRC32c checksums of the written data.
+/// Both lanes are mixed by a finalizer so every bit of the 64 bit output depends on the data,
+/// unlike [CRC32Hasher](super::CRC32Hasher) whose high 32 bits are always cleared.
+pub type CRC32WideHasher = WideHasher<CRC32Lanes>;
+
+/// A builder for default [CRC32WideHasher].
+pub type CRC32WideBuildHasher = BuildHasherDefault<CRC32WideHasher>;
@@ -0,0 +1,51 @@
+//!
+//! Shift tables of the CRC32c register used to merge interleaved streams.
+//! Appending `len` zero bytes to a stream multiplies its register by `x^(8 * len)` modulo the polynomial,
+//! a linear operation which is tabulated for each byte of the register.
+use crate::combine;
+
+/// Generate the tables shifting the reflected register by `len` zero bytes.
+/// The given polynomial is reversed before the generation
+pub(super) const fn generate_shift_table(polynomial: u32, len: u64) -> [[u32; 256]; 4] {
+    let reversed_polynomial = polynomial.reverse_bits() as u128;
+    let x_8n = combine::x_pow_8n(len, reversed_polynomial, 32);
+
+    let mut shift_table = [[0u32; 256]; 4];
+    let mut n = 0;
+    while n < 256 {
+        let mut k = 0;
+        while k < 4 {
+            let byte = ((n as u32) << (8 * k)) as u128;
+            shift_table[k][n] = combine::multiply(byte, x_8n, reversed_polynomial, 32) as u32;
+            k += 1;
+        }
+        n += 1;
+    }
+    shift_table
+}
+
+/// Shifts the reflected register with a table generated by [generate_shift_table]
+#[inline(always)]
+pub(super) fn shift(crc: u32, shift_table: &[[u32; 256]; 4]) -> u32 {
+    shift_table[0][(crc & 0xFF) as usize]
+        ^ shift_table[1][((crc >> 8) & 0xFF) as usize]
+        ^ shift_table[2][((crc >> 16) & 0xFF) as usize]
+        ^ shift_table[3][(crc >> 24) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_shift_table, shift};
+    use crate::crc32c;
+
+    #[test]
+    fn shift_is_coherent_with_zero_bytes() {
+        const SHIFT_TABLE: [[u32; 256]; 4] = generate_shift_table(crc32c::POLYNOMIAL, 100);
+        for crc in [0, 1, 0x80000000, 0x12345678, 0xFFFFFFFF] {
+            assert_eq!(
+                shift(crc, &SHIFT_TABLE),
+                crate::algorithm::update(crc, &[0; 100], &crc32c::LOOKUP_TABLE)
+            );
+        }
+    }
+}
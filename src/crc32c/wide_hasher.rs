@@ -0,0 +1,28 @@
+use crate::wide_hasher::{Lanes, WideHasher};
+use core::hash::BuildHasherDefault;
+
+/// Lanes of [CRC32CWideHasher]: the CRC32c then the CRC32 checksums
+#[derive(Debug, Default)]
+pub struct CRC32CLanes;
+
+impl Lanes for CRC32CLanes {
+    #[inline(always)]
+    fn key(bytes: &[u8], key: u32) -> u32 {
+        crate::crc32c::slice_by_8_with_seed(bytes, key)
+    }
+
+    #[inline(always)]
+    fn other_key(bytes: &[u8], other_key: u32) -> u32 {
+        crate::crc32::slice_by_8_with_seed(bytes, other_key)
+    }
+}
+
+/// Slice-By-8 hasher with a 64 bit output
+///
+/// The hash has two lanes: the CRC32c and the CRC32 checksums of the written data.
+/// Both lanes are mixed by a finalizer so every bit of the 64 bit output depends on the data,
+/// unlike [CRC32CHasher](super::CRC32CHasher) whose high 32 bits are always cleared.
+pub type CRC32CWideHasher = WideHasher<CRC32CLanes>;
+
+/// A builder for default [CRC32CWideHasher].
+pub type CRC32CWideBuildHasher = BuildHasherDefault<CRC32CWideHasher>;
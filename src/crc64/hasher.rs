@@ -0,0 +1,8 @@
+crate::hasher::hasher!(
+    crc64,
+    CRC64Hasher,
+    CRC64BuildHasher,
+    CRC64SeededBuildHasher,
+    u64,
+    seed: 0x9B9BEFFB9B9BEFFB
+);
@@ -0,0 +1,244 @@
+use crate::{generate_table_u64, Crc64Params};
+
+mod hasher;
+pub use hasher::{CRC64BuildHasher, CRC64Hasher, CRC64SeededBuildHasher};
+
+/// Polynomial used to generate the [LOOKUP_TABLE]
+/// 
+/// # Example
+/// ```
+/// use hud_slice_by_8::crc64;
+/// assert_eq!(crc64::POLYNOMIAL, 0x42F0E1EBA9EA3693)
+/// ```
+pub const POLYNOMIAL: u64 = 0x42F0E1EBA9EA3693;
+
+/// Lookup table generated at compile time with the [POLYNOMIAL]
+/// 
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc64,generate_table_u64};
+///
+/// assert_eq!(generate_table_u64(crc64::POLYNOMIAL), crc64::LOOKUP_TABLE);
+/// ```
+pub const LOOKUP_TABLE: [[u64; 256]; 8] = generate_table_u64(POLYNOMIAL);
+
+/// Parameters of the CRC-64/XZ model computed by [slice_by_8]
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc64, Crc64};
+///
+/// const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+/// assert_eq!(Crc64::new(crc64::PARAMS).checksum(HASH_ME), crc64::slice_by_8(HASH_ME));
+/// ```
+pub const PARAMS: Crc64Params = Crc64Params {
+    poly: POLYNOMIAL,
+    init: 0xFFFFFFFFFFFFFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFFFFFFFFFFFFFF,
+    check: 0x995DC9BBDF1939FA,
+};
+
+/// Parameters of the CRC-64/ECMA-182 model
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc64, Crc64};
+///
+/// assert_eq!(Crc64::new(crc64::ECMA_182).checksum(b"123456789"), 0x6C40DF5F0B497347);
+/// ```
+pub const ECMA_182: Crc64Params = Crc64Params {
+    poly: POLYNOMIAL,
+    init: 0x0000000000000000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000000000000000,
+    check: 0x6C40DF5F0B497347,
+};
+
+/// Parameters of the CRC-64/NVME model
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc64, Crc64};
+///
+/// assert_eq!(Crc64::new(crc64::NVME).checksum(b"123456789"), 0xAE8B14860A799888);
+/// ```
+pub const NVME: Crc64Params = Crc64Params {
+    poly: 0xAD93D23594C93659,
+    init: 0xFFFFFFFFFFFFFFFF,
+    refin: true,
+    refout: true,
+    xorout: 0xFFFFFFFFFFFFFFFF,
+    check: 0xAE8B14860A799888,
+};
+
+/// Computes the CRC64 checksum for the specified buffer using the slicing by 8
+/// algorithm over 64 bit quantities.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::crc64;
+///
+/// const HASH_ME : &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+/// assert_eq!(crc64::slice_by_8(HASH_ME), 0x26967875751B122F);
+/// ```
+#[inline(always)]
+pub fn slice_by_8(buf: &[u8]) -> u64 {
+    slice_by_8_with_seed(buf, 0)
+}
+
+/// Computes the CRC64 checksum for the specified buffer using the slicing by 8
+/// algorithm over 64 bit quantities, adding a seed to the result.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::crc64;
+///
+/// const HASH_ME : &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+/// assert_eq!(crc64::slice_by_8_with_seed(HASH_ME, 123456789), 0xE7A3ABC4AADA453A);
+/// ```
+#[inline(always)]
+pub fn slice_by_8_with_seed(buf: &[u8], seed: u64) -> u64 {
+    !update(!seed, buf)
+}
+
+/// Updates the register of the CRC64 checksum with the buffer,
+/// with the fastest implementation enabled at compile time or detected at runtime.
+#[inline(always)]
+#[cfg(not(any(
+    all(target_arch = "aarch64", target_feature = "aes"),
+    all(feature = "std", target_arch = "aarch64")
+)))]
+pub(crate) fn update(crc: u64, buf: &[u8]) -> u64 {
+    crate::algorithm::update(crc, buf, &LOOKUP_TABLE)
+}
+
+#[inline(always)]
+#[cfg(all(target_arch = "aarch64", target_feature = "aes"))]
+pub(crate) fn update(crc: u64, buf: &[u8]) -> u64 {
+    // SAFETY: aes is enabled at compile time
+    unsafe { update_pmull(crc, buf) }
+}
+
+#[inline(always)]
+#[cfg(all(feature = "std", target_arch = "aarch64", not(target_feature = "aes")))]
+pub(crate) fn update(crc: u64, buf: &[u8]) -> u64 {
+    DISPATCH.update(crc, buf)
+}
+
+/// Folding with `pmull` when the CPU supports it, the [LOOKUP_TABLE] otherwise
+#[cfg(all(feature = "std", target_arch = "aarch64", not(target_feature = "aes")))]
+static DISPATCH: crate::detect::Dispatch<u64> = crate::detect::Dispatch::new(
+    || std::arch::is_aarch64_feature_detected!("pmull"),
+    // SAFETY: pmull is detected
+    |crc, buf| unsafe { update_pmull(crc, buf) },
+    |crc, buf| crate::algorithm::update(crc, buf, &LOOKUP_TABLE),
+);
+
+/// Constants of the carry-less multiplication folding derived from the [POLYNOMIAL]
+#[cfg(all(
+    target_arch = "aarch64",
+    any(target_feature = "aes", feature = "std")
+))]
+const FOLD_KEYS: crate::fold::FoldKeys = crate::fold::FoldKeys::new(POLYNOMIAL, 64);
+
+/// Updates the register of the CRC64 checksum by folding 128 bits blocks with the NEON `pmull` instruction.
+/// Short buffers and tails are hashed with the [LOOKUP_TABLE].
+///
+/// # Safety
+/// The CPU must support `pmull`.
+#[cfg(all(
+    target_arch = "aarch64",
+    any(target_feature = "aes", feature = "std")
+))]
+#[target_feature(enable = "neon,aes")]
+unsafe fn update_pmull(crc: u64, buf: &[u8]) -> u64 {
+    crate::fold::aarch64::update(crc, buf, &FOLD_KEYS, &LOOKUP_TABLE)
+}
+
+/// Computes the CRC64 checksum of the concatenation of two blocks from the checksum of each
+/// block without hashing them again. `len_b` is the length of the second block.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::crc64;
+///
+/// let crc_a = crc64::slice_by_8(b"abcdefghijklm");
+/// let crc_b = crc64::slice_by_8(b"nopqrstuvwxyz");
+///
+/// assert_eq!(crc64::combine(crc_a, crc_b, 13), crc64::slice_by_8(b"abcdefghijklmnopqrstuvwxyz"));
+/// ```
+pub fn combine(crc_a: u64, crc_b: u64, len_b: u64) -> u64 {
+    crate::combine(crc_a, crc_b, len_b, POLYNOMIAL)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{crc64, generate_table_u64};
+
+    #[test]
+    fn slice_by_8_no_seed() {
+        const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+        assert_eq!(crc64::slice_by_8(HASH_ME), 0x26967875751B122F);
+    }
+
+    #[test]
+    fn slice_by_8_with_seed() {
+        const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+        assert_eq!(
+            crc64::slice_by_8_with_seed(HASH_ME, 123456789),
+            0xE7A3ABC4AADA453A
+        );
+    }
+
+    #[test]
+    fn lookup_table_is_correct() {
+        assert_eq!(generate_table_u64(crc64::POLYNOMIAL), crc64::LOOKUP_TABLE);
+        assert_eq!(crc64::LOOKUP_TABLE[0][1], 0xB32E4CBE03A75F6F);
+    }
+
+    #[test]
+    fn slice_by_8_is_coherent_with_bytewise_algorithm() {
+        const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog, 0123456789";
+        for start in 0..8 {
+            for end in start..HASH_ME.len() {
+                let buf = &HASH_ME[start..end];
+                let bytewise = !buf.iter().fold(!0u64, |acc, byte| {
+                    (acc >> 8) ^ crc64::LOOKUP_TABLE[0][((acc ^ *byte as u64) & 0xFF) as usize]
+                });
+                assert_eq!(crc64::slice_by_8(buf), bytewise);
+            }
+        }
+    }
+
+    #[test]
+    fn slice_by_8_of_large_buffer_is_coherent_with_lookup_table() {
+        let mut buf = [0u8; 1024];
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = (i * 31) as u8;
+        }
+        for start in 0..16 {
+            for end in (start..buf.len()).step_by(7) {
+                let buf = &buf[start..end];
+                assert_eq!(
+                    crc64::slice_by_8_with_seed(buf, 123456789),
+                    !crate::algorithm::update(!123456789, buf, &crc64::LOOKUP_TABLE)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn combine_is_coherent_with_slice_by_8() {
+        const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+        for split in 0..=HASH_ME.len() {
+            let (a, b) = HASH_ME.split_at(split);
+            assert_eq!(
+                crc64::combine(crc64::slice_by_8(a), crc64::slice_by_8(b), b.len() as u64),
+                crc64::slice_by_8(HASH_ME)
+            );
+        }
+    }
+}
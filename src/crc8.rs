@@ -0,0 +1,100 @@
+//! CRC-8 models computed with the slicing by 8 algorithm.
+//!
+//! Every model provides its [Crc8Params](crate::Crc8Params), a lookup table generated at compile
+//! time and a `slice_by_8` function. Parameters are verified against their check value at
+//! compile time.
+//!
+//! # Example
+//! ```
+//! use hud_slice_by_8::crc8;
+//!
+//! assert_eq!(crc8::smbus::slice_by_8(b"123456789"), 0xF4);
+//! assert_eq!(crc8::maxim_dow::slice_by_8(b"123456789"), 0xA1);
+//! ```
+use crate::models::model;
+
+model!(
+    crc8: u8,
+    /// CRC-8/AUTOSAR model
+    autosar,
+    poly: 0x2F,
+    init: 0xFF,
+    refin: false,
+    refout: false,
+    xorout: 0xFF,
+    check: 0xDF
+);
+
+model!(
+    crc8: u8,
+    /// CRC-8/BLUETOOTH model
+    bluetooth,
+    poly: 0xA7,
+    init: 0x00,
+    refin: true,
+    refout: true,
+    xorout: 0x00,
+    check: 0x26
+);
+
+model!(
+    crc8: u8,
+    /// CRC-8/MAXIM-DOW (Dallas/Maxim 1-Wire) model
+    maxim_dow,
+    poly: 0x31,
+    init: 0x00,
+    refin: true,
+    refout: true,
+    xorout: 0x00,
+    check: 0xA1
+);
+
+model!(
+    crc8: u8,
+    /// CRC-8/SMBUS model
+    smbus,
+    poly: 0x07,
+    init: 0x00,
+    refin: false,
+    refout: false,
+    xorout: 0x00,
+    check: 0xF4
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{crc8, generate_normal_table_u8, generate_table_u8};
+
+    #[test]
+    fn slice_by_8_is_coherent_with_reference() {
+        const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(crc8::autosar::slice_by_8(HASH_ME), 0x67);
+        assert_eq!(crc8::bluetooth::slice_by_8(HASH_ME), 0xAE);
+        assert_eq!(crc8::maxim_dow::slice_by_8(HASH_ME), 0x16);
+        assert_eq!(crc8::smbus::slice_by_8(HASH_ME), 0xC1);
+    }
+
+    #[test]
+    fn slice_by_8_with_seed_and_combine_are_coherent_with_slice_by_8() {
+        const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog";
+        for split in [0, 1, 20, HASH_ME.len()] {
+            let (a, b) = HASH_ME.split_at(split);
+            type Model = (fn(&[u8]) -> u8, fn(&[u8], u8) -> u8, fn(u8, u8, u64) -> u8);
+            let models: [Model; 2] = [
+                (crc8::autosar::slice_by_8, crc8::autosar::slice_by_8_with_seed, crc8::autosar::combine),
+                (crc8::maxim_dow::slice_by_8, crc8::maxim_dow::slice_by_8_with_seed, crc8::maxim_dow::combine),
+            ];
+            for (slice_by_8, slice_by_8_with_seed, combine) in models {
+                assert_eq!(slice_by_8_with_seed(b, slice_by_8(a)), slice_by_8(HASH_ME));
+                let (crc_a, crc_b) = (slice_by_8(a), slice_by_8(b));
+                assert_eq!(combine(crc_a, crc_b, b.len() as u64), slice_by_8(HASH_ME));
+            }
+        }
+    }
+
+    #[test]
+    fn lookup_tables_are_correct() {
+        assert_eq!(crc8::maxim_dow::LOOKUP_TABLE, generate_table_u8(0x31));
+        assert_eq!(crc8::smbus::LOOKUP_TABLE, generate_normal_table_u8(0x07));
+    }
+}
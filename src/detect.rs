@@ -0,0 +1,110 @@
+//!
+//! Runtime detection of the CPU features of the accelerated kernels.
+//!
+//! A feature is detected at first use and cached, the kernels of the CRC modules
+//! are selected from the cached feature.
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// The feature has not been detected yet
+const UNKNOWN: u8 = 0;
+/// The CPU does not support the feature
+const UNSUPPORTED: u8 = 1;
+/// The CPU supports the feature
+const SUPPORTED: u8 = 2;
+
+/// CPU feature detected at first use and cached
+pub(crate) struct Feature {
+    state: AtomicU8,
+    detect: fn() -> bool,
+}
+
+impl Feature {
+    /// Create a new [Feature] detected by the predicate
+    pub(crate) const fn new(detect: fn() -> bool) -> Feature {
+        Feature {
+            state: AtomicU8::new(UNKNOWN),
+            detect,
+        }
+    }
+
+    /// Returns `true` if the CPU supports the feature, the predicate only runs at first call
+    #[inline(always)]
+    pub(crate) fn is_supported(&self) -> bool {
+        match self.state.load(Ordering::Relaxed) {
+            SUPPORTED => true,
+            UNSUPPORTED => false,
+            _ => self.detect(),
+        }
+    }
+
+    /// Run the predicate and cache its result
+    #[cold]
+    fn detect(&self) -> bool {
+        let supported = (self.detect)();
+        let state = if supported { SUPPORTED } else { UNSUPPORTED };
+        self.state.store(state, Ordering::Relaxed);
+        supported
+    }
+}
+
+/// Updates a register with a buffer
+pub(crate) type Update<W> = fn(W, &[u8]) -> W;
+
+/// Kernel updating a register with the accelerated implementation when the CPU supports its feature,
+/// with the portable implementation otherwise
+pub(crate) struct Dispatch<W> {
+    feature: Feature,
+    accelerated: Update<W>,
+    portable: Update<W>,
+}
+
+impl<W> Dispatch<W> {
+    /// Create a new [Dispatch] selecting the accelerated kernel when the predicate holds
+    pub(crate) const fn new(
+        detect: fn() -> bool,
+        accelerated: Update<W>,
+        portable: Update<W>,
+    ) -> Dispatch<W> {
+        Dispatch {
+            feature: Feature::new(detect),
+            accelerated,
+            portable,
+        }
+    }
+
+    /// Updates the register with the fastest implementation supported by the CPU
+    #[inline(always)]
+    pub(crate) fn update(&self, crc: W, buf: &[u8]) -> W {
+        if self.feature.is_supported() {
+            (self.accelerated)(crc, buf)
+        } else {
+            (self.portable)(crc, buf)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dispatch, Feature};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn feature_is_detected_once() {
+        static DETECTIONS: AtomicUsize = AtomicUsize::new(0);
+        static FEATURE: Feature = Feature::new(|| {
+            DETECTIONS.fetch_add(1, Ordering::Relaxed);
+            true
+        });
+        assert!(FEATURE.is_supported());
+        assert!(FEATURE.is_supported());
+        assert_eq!(DETECTIONS.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn dispatch_selects_the_kernel_of_the_detected_feature() {
+        static ACCELERATED: Dispatch<u32> = Dispatch::new(|| true, |crc, _| crc + 1, |crc, _| crc);
+        static PORTABLE: Dispatch<u32> = Dispatch::new(|| false, |crc, _| crc + 1, |crc, _| crc);
+        assert_eq!(ACCELERATED.update(1, b""), 2);
+        assert_eq!(PORTABLE.update(1, b""), 1);
+    }
+}
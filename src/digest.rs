@@ -0,0 +1,235 @@
+//!
+//! Incremental checksum shared by the CRC32 and CRC32c modules.
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+
+/// CRC32 variant computed by a [Digest]
+///
+/// Implemented by [CRC32Kernel](crate::crc32::CRC32Kernel) and [CRC32CKernel](crate::crc32c::CRC32CKernel).
+pub trait Kernel {
+    /// Updates the register with the buffer.
+    /// The register is the inverted checksum, `!update(!seed, buf)` is the checksum seeded with `seed`.
+    fn update(crc: u32, buf: &[u8]) -> u32;
+}
+
+/// Incremental CRC32 checksum computed by the [Kernel]
+///
+/// The checksum of the data fed with [Digest::update] is the checksum of their concatenation.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::crc32;
+///
+/// let mut digest = crc32::Digest::new();
+/// digest.update(b"abcdefghijklm");
+/// digest.update(b"nopqrstuvwxyz");
+///
+/// assert_eq!(digest.finalize(), crc32::slice_by_8(b"abcdefghijklmnopqrstuvwxyz"));
+/// assert_eq!(digest.bytes_processed(), 26);
+/// ```
+pub struct Digest<K> {
+    initial: u32,
+    /// Register of the checksum, inverted from the checksum
+    crc: u32,
+    bytes_processed: u64,
+    kernel: PhantomData<fn() -> K>,
+}
+
+impl<K: Kernel> Digest<K> {
+    /// Create a new [Digest] of an empty buffer
+    pub const fn new() -> Digest<K> {
+        Digest::with_initial(0)
+    }
+
+    /// Create a new [Digest] resuming from the checksum of previous data
+    ///
+    /// # Example
+    /// ```
+    /// use hud_slice_by_8::crc32;
+    ///
+    /// let mut digest = crc32::Digest::with_initial(crc32::slice_by_8(b"abcdefghijklm"));
+    /// digest.update(b"nopqrstuvwxyz");
+    ///
+    /// assert_eq!(digest.finalize(), crc32::slice_by_8(b"abcdefghijklmnopqrstuvwxyz"));
+    /// ```
+    pub const fn with_initial(initial: u32) -> Digest<K> {
+        Digest {
+            initial,
+            crc: !initial,
+            bytes_processed: 0,
+            kernel: PhantomData,
+        }
+    }
+
+    /// Feeds the buffer to the [Digest]
+    pub fn update(&mut self, buf: &[u8]) {
+        self.crc = K::update(self.crc, buf);
+        self.bytes_processed += buf.len() as u64;
+    }
+
+    /// Returns the checksum of the data fed so far
+    pub fn finalize(&self) -> u32 {
+        !self.crc
+    }
+
+    /// Restores the [Digest] to its initial checksum
+    pub fn reset(&mut self) {
+        self.crc = !self.initial;
+        self.bytes_processed = 0;
+    }
+
+    /// Returns the number of bytes fed since the creation or the last [Digest::reset]
+    pub fn bytes_processed(&self) -> u64 {
+        self.bytes_processed
+    }
+}
+
+impl<K: Kernel> Default for Digest<K> {
+    fn default() -> Digest<K> {
+        Digest::new()
+    }
+}
+
+impl<K> Clone for Digest<K> {
+    fn clone(&self) -> Digest<K> {
+        *self
+    }
+}
+
+impl<K> Copy for Digest<K> {}
+
+impl<K> PartialEq for Digest<K> {
+    fn eq(&self, other: &Digest<K>) -> bool {
+        (self.initial, self.crc, self.bytes_processed)
+            == (other.initial, other.crc, other.bytes_processed)
+    }
+}
+
+impl<K> Eq for Digest<K> {}
+
+impl<K> Debug for Digest<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Digest")
+            .field("initial", &self.initial)
+            .field("crc", &self.crc)
+            .field("bytes_processed", &self.bytes_processed)
+            .finish()
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<K: Kernel> digest::HashMarker for Digest<K> {}
+
+#[cfg(feature = "digest")]
+impl<K: Kernel> digest::OutputSizeUser for Digest<K> {
+    type OutputSize = digest::consts::U4;
+}
+
+#[cfg(feature = "digest")]
+impl<K: Kernel> digest::Update for Digest<K> {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(self, data);
+    }
+}
+
+/// The output is the checksum in big endian order, as it is usually displayed
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::crc32;
+/// use digest::Digest;
+///
+/// let output = crc32::Digest::digest(b"abcdefghijklmnopqrstuvwxyz");
+///
+/// assert_eq!(output[..], u32::to_be_bytes(0x4C2750BD));
+/// ```
+#[cfg(feature = "digest")]
+impl<K: Kernel> digest::FixedOutput for Digest<K> {
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&Digest::finalize(&self).to_be_bytes());
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<K: Kernel> digest::Reset for Digest<K> {
+    fn reset(&mut self) {
+        Digest::reset(self);
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<K: Kernel> digest::FixedOutputReset for Digest<K> {
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&Digest::finalize(self).to_be_bytes());
+        Digest::reset(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Digest, Kernel};
+    use crate::crc32::CRC32Kernel;
+    use crate::crc32c::CRC32CKernel;
+    use crate::{crc32, crc32c};
+
+    const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog, 0123456789";
+
+    fn digest_new_is_empty<K: Kernel>(slice_by_8: fn(&[u8]) -> u32) {
+        assert_eq!(Digest::<K>::default(), Digest::new());
+        let digest = Digest::<K>::new();
+        assert_eq!(digest.finalize(), slice_by_8(b""));
+        assert_eq!(digest.bytes_processed(), 0);
+    }
+
+    fn digest_is_coherent_with_slice_by_8<K: Kernel>(slice_by_8: fn(&[u8]) -> u32) {
+        for split in 0..=HASH_ME.len() {
+            let (a, b) = HASH_ME.split_at(split);
+            let mut digest = Digest::<K>::new();
+            digest.update(a);
+            digest.update(b);
+            assert_eq!(digest.finalize(), slice_by_8(HASH_ME));
+            assert_eq!(digest.bytes_processed(), HASH_ME.len() as u64);
+        }
+    }
+
+    fn digest_reset_restores_initial_checksum<K: Kernel>(
+        slice_by_8_with_seed: fn(&[u8], u32) -> u32,
+    ) {
+        let mut digest = Digest::<K>::with_initial(123456789);
+        digest.update(HASH_ME);
+        assert_eq!(digest.finalize(), slice_by_8_with_seed(HASH_ME, 123456789));
+
+        digest.reset();
+        assert_eq!(digest, Digest::with_initial(123456789));
+        digest.update(HASH_ME);
+        assert_eq!(digest.finalize(), slice_by_8_with_seed(HASH_ME, 123456789));
+    }
+
+    #[test]
+    fn digests_are_coherent_with_slice_by_8() {
+        digest_new_is_empty::<CRC32Kernel>(crc32::slice_by_8);
+        digest_new_is_empty::<CRC32CKernel>(crc32c::slice_by_8);
+        digest_is_coherent_with_slice_by_8::<CRC32Kernel>(crc32::slice_by_8);
+        digest_is_coherent_with_slice_by_8::<CRC32CKernel>(crc32c::slice_by_8);
+        digest_reset_restores_initial_checksum::<CRC32Kernel>(crc32::slice_by_8_with_seed);
+        digest_reset_restores_initial_checksum::<CRC32CKernel>(crc32c::slice_by_8_with_seed);
+    }
+
+    #[test]
+    #[cfg(feature = "digest")]
+    fn digest_traits_are_coherent_with_slice_by_8() {
+        use digest::{FixedOutputReset, Update};
+
+        let mut digest = crc32::Digest::new();
+        Update::update(&mut digest, HASH_ME);
+        let output = digest.finalize_fixed_reset();
+        assert_eq!(output[..], crc32::slice_by_8(HASH_ME).to_be_bytes());
+        assert_eq!(digest, crc32::Digest::new());
+
+        let mut digest = crc32c::Digest::new();
+        Update::update(&mut digest, HASH_ME);
+        let output = digest.finalize_fixed_reset();
+        assert_eq!(output[..], crc32c::slice_by_8(HASH_ME).to_be_bytes());
+        assert_eq!(digest, crc32c::Digest::new());
+    }
+}
@@ -0,0 +1,39 @@
+//!
+//! Checksum of files.
+//! Regular files are memory-mapped and hashed as a single buffer,
+//! pipes and special files that can not be mapped are read through a buffer.
+use std::fs::File;
+use std::io::{ErrorKind, Read, Result};
+use std::path::Path;
+
+/// Size of the buffer of files that can not be mapped
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Computes the checksum of the file with `slice_by_8` if it can be mapped,
+/// otherwise chunk by chunk with `slice_by_8_with_seed`.
+pub(crate) fn checksum_file(
+    path: &Path,
+    slice_by_8: impl Fn(&[u8]) -> u32,
+    slice_by_8_with_seed: impl Fn(&[u8], u32) -> u32,
+) -> Result<u32> {
+    let mut file = File::open(path)?;
+    let metadata = file.metadata()?;
+    // Empty files can not be mapped on every platform
+    if metadata.is_file() && metadata.len() > 0 {
+        // SAFETY: the file must not be modified while it is mapped, as for any memory-mapped file
+        if let Ok(map) = unsafe { memmap2::Mmap::map(&file) } {
+            return Ok(slice_by_8(&map));
+        }
+    }
+
+    let mut buf = std::vec![0u8; BUFFER_SIZE];
+    let mut crc = 0;
+    loop {
+        match file.read(&mut buf) {
+            Ok(0) => return Ok(crc),
+            Ok(read) => crc = slice_by_8_with_seed(&buf[..read], crc),
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//!
+//! Folding with the NEON `pmull` instruction.
+use super::FoldKeys;
+use crate::CrcWidth;
+use core::arch::aarch64::*;
+
+/// Buffers shorter than 128 bytes are hashed with the lookup table
+const MIN_LEN: usize = 128;
+
+/// Returns `true` if the CPU supports `pmull`
+#[inline(always)]
+#[cfg(target_feature = "aes")]
+pub(crate) fn is_supported() -> bool {
+    true
+}
+
+/// Returns `true` if the CPU supports `pmull`, detected at first call
+#[inline(always)]
+#[cfg(not(target_feature = "aes"))]
+pub(crate) fn is_supported() -> bool {
+    PMULL.is_supported()
+}
+
+/// `pmull` detected once for every engine
+#[cfg(not(target_feature = "aes"))]
+static PMULL: crate::detect::Feature =
+    crate::detect::Feature::new(|| std::arch::is_aarch64_feature_detected!("pmull"));
+
+/// Updates the reflected CRC register of at most 64 bits with the buffer.
+/// The last 128 bits and the tail shorter than 16 bytes are hashed with the lookup table.
+///
+/// # Safety
+/// The CPU must support `pmull`.
+#[target_feature(enable = "neon,aes")]
+pub(crate) unsafe fn update<W: CrcWidth>(
+    crc: W,
+    mut buf: &[u8],
+    keys: &FoldKeys,
+    lookup_table: &[[W; 256]; 8],
+) -> W {
+    debug_assert!(W::BITS <= 64);
+    if buf.len() < MIN_LEN {
+        return crate::algorithm::update(crc, buf, lookup_table);
+    }
+
+    // Fold 4 x 128 bits at once
+    let mut x3 = load(&mut buf);
+    let mut x2 = load(&mut buf);
+    let mut x1 = load(&mut buf);
+    let mut x0 = load(&mut buf);
+    x3 = veorq_u64(x3, vcombine_u64(vcreate_u64(crc.low_u64()), vcreate_u64(0)));
+
+    while buf.len() >= 64 {
+        x3 = fold(x3, load(&mut buf), keys.fold_512);
+        x2 = fold(x2, load(&mut buf), keys.fold_512);
+        x1 = fold(x1, load(&mut buf), keys.fold_512);
+        x0 = fold(x0, load(&mut buf), keys.fold_512);
+    }
+
+    // Fold 4 x 128 bits into 128 bits, then the remaining 128 bits blocks
+    let mut x = fold(x3, x2, keys.fold_128);
+    x = fold(x, x1, keys.fold_128);
+    x = fold(x, x0, keys.fold_128);
+    while buf.len() >= 16 {
+        x = fold(x, load(&mut buf), keys.fold_128);
+    }
+
+    // The folded 128 bits have the CRC of the buffer read so far, starting from a cleared register
+    let mut folded = [0u8; 16];
+    vst1q_u8(folded.as_mut_ptr(), vreinterpretq_u8_u64(x));
+    let crc = crate::algorithm::update(W::ZERO, &folded, lookup_table);
+
+    // Consume remaining 0 to 15 bytes
+    crate::algorithm::update(crc, buf, lookup_table)
+}
+
+/// Loads 128 bits and advances the buffer
+#[inline(always)]
+unsafe fn load(buf: &mut &[u8]) -> uint64x2_t {
+    let block = vreinterpretq_u64_u8(vld1q_u8(buf.as_ptr()));
+    *buf = &buf[16..];
+    block
+}
+
+/// Folds `a` over `b`: multiplies each half of `a` by its constant and adds `b`
+#[inline]
+#[target_feature(enable = "neon,aes")]
+unsafe fn fold(a: uint64x2_t, b: uint64x2_t, keys: [u64; 2]) -> uint64x2_t {
+    let low = vreinterpretq_u64_p128(vmull_p64(vgetq_lane_u64::<0>(a), keys[0]));
+    let high = vreinterpretq_u64_p128(vmull_p64(vgetq_lane_u64::<1>(a), keys[1]));
+    veorq_u64(veorq_u64(b, low), high)
+}
@@ -0,0 +1,224 @@
+//!
+//! Folding of 128 bit blocks with carry-less multiplication.
+//! Implementation of "*Fast CRC Computation for Generic Polynomials Using PCLMULQDQ Instruction*"
+//! by Intel for reflected polynomials.
+//! The buffer is folded 4 x 128 bits at once then 128 bits at once.
+//! On `x86_64` the remaining 128 bits are reduced to the 32 bits CRC with a Barrett reduction,
+//! on `aarch64` they are hashed with the lookup table, which works with any width up to 64 bits.
+#[cfg(target_arch = "aarch64")]
+pub(crate) mod aarch64;
+#[cfg(target_arch = "x86_64")]
+pub(crate) mod x86_64;
+
+/// Constants of the folding with a Barrett reduction derived from a 32 bits polynomial.
+/// Every constant is bit-reflected on 33 bits to be multiplied with reflected data.
+#[cfg(target_arch = "x86_64")]
+pub(crate) struct FoldConstants {
+    /// `x^(4*128+32) mod P`, folds the low half of 4 x 128 bits
+    pub(crate) k1: u64,
+    /// `x^(4*128-32) mod P`, folds the high half of 4 x 128 bits
+    pub(crate) k2: u64,
+    /// `x^(128+32) mod P`, folds the low half of 128 bits
+    pub(crate) k3: u64,
+    /// `x^(128-32) mod P`, folds the high half of 128 bits
+    pub(crate) k4: u64,
+    /// `x^64 mod P`, reduces 96 bits to 64 bits
+    pub(crate) k5: u64,
+    /// `P` with its `x^32` term
+    pub(crate) p: u64,
+    /// `x^64 / P`, quotient used by the Barrett reduction
+    pub(crate) u: u64,
+}
+
+#[cfg(target_arch = "x86_64")]
+impl FoldConstants {
+    /// Derive the constants from the (non reversed) polynomial
+    pub(crate) const fn new(polynomial: u32) -> Self {
+        Self {
+            k1: reflected_x_pow_mod(4 * 128 + 32, polynomial),
+            k2: reflected_x_pow_mod(4 * 128 - 32, polynomial),
+            k3: reflected_x_pow_mod(128 + 32, polynomial),
+            k4: reflected_x_pow_mod(128 - 32, polynomial),
+            k5: reflected_x_pow_mod(64, polynomial),
+            p: ((polynomial.reverse_bits() as u64) << 1) | 1,
+            u: x_pow_64_div(polynomial).reverse_bits() >> 31,
+        }
+    }
+}
+
+/// Constants of the folding derived from a polynomial of any width up to 64 bits.
+/// Each pair multiplies the low and the high half of 128 bits,
+/// every constant is bit-reflected on 64 bits to be multiplied with reflected data.
+#[cfg(any(target_arch = "aarch64", test))]
+#[derive(Debug, Clone)]
+pub(crate) struct FoldKeys {
+    /// `x^(4*128+63) mod P` and `x^(4*128-1) mod P`, folds 4 x 128 bits
+    pub(crate) fold_512: [u64; 2],
+    /// `x^(128+63) mod P` and `x^(128-1) mod P`, folds 128 bits
+    pub(crate) fold_128: [u64; 2],
+}
+
+#[cfg(any(target_arch = "aarch64", test))]
+impl FoldKeys {
+    /// Keys of the engines that are not folded
+    #[cfg(target_arch = "aarch64")]
+    pub(crate) const UNUSED: FoldKeys = FoldKeys {
+        fold_512: [0; 2],
+        fold_128: [0; 2],
+    };
+
+    /// Derive the constants from the (non reversed) polynomial of `width` bits
+    pub(crate) const fn new(polynomial: u64, width: u32) -> Self {
+        Self {
+            fold_512: [
+                x_pow_mod(4 * 128 + 63, polynomial, width).reverse_bits(),
+                x_pow_mod(4 * 128 - 1, polynomial, width).reverse_bits(),
+            ],
+            fold_128: [
+                x_pow_mod(128 + 63, polynomial, width).reverse_bits(),
+                x_pow_mod(128 - 1, polynomial, width).reverse_bits(),
+            ],
+        }
+    }
+}
+
+/// Computes `x^exponent mod P` where `P` is the polynomial of `width` bits
+const fn x_pow_mod(exponent: u32, polynomial: u64, width: u32) -> u64 {
+    let most_significant_bit = 1u64 << (width - 1);
+    let mask = u64::MAX >> (64 - width);
+    let mut remainder = 1u64;
+    let mut i = 0;
+    while i < exponent {
+        remainder = if remainder & most_significant_bit != 0 {
+            ((remainder << 1) ^ polynomial) & mask
+        } else {
+            remainder << 1
+        };
+        i += 1;
+    }
+    remainder
+}
+
+/// Computes `x^exponent mod P` reflected on 33 bits
+#[cfg(target_arch = "x86_64")]
+const fn reflected_x_pow_mod(exponent: u32, polynomial: u32) -> u64 {
+    x_pow_mod(exponent, polynomial as u64, 32).reverse_bits() >> 31
+}
+
+/// Computes the quotient of `x^64 / P`
+#[cfg(target_arch = "x86_64")]
+const fn x_pow_64_div(polynomial: u32) -> u64 {
+    let divisor = (1u128 << 32) | polynomial as u128;
+    let mut dividend = 1u128 << 64;
+    let mut quotient = 0u64;
+    let mut shift = 32;
+    loop {
+        if dividend & (1u128 << (32 + shift)) != 0 {
+            quotient |= 1 << shift;
+            dividend ^= divisor << shift;
+        }
+        if shift == 0 {
+            break;
+        }
+        shift -= 1;
+    }
+    quotient
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::FoldKeys;
+    use crate::{generate_table, generate_table_u16, generate_table_u64, generate_table_u8, CrcWidth};
+
+    /// Carry-less multiplication of two 64 bits polynomials
+    fn clmul(a: u64, b: u64) -> u128 {
+        (0..64)
+            .filter(|bit| b >> bit & 1 == 1)
+            .fold(0, |product, bit| product ^ ((a as u128) << bit))
+    }
+
+    /// Software model of the `aarch64` folding
+    fn fold_model<W: CrcWidth>(crc: W, buf: &[u8], keys: &FoldKeys, lookup_table: &[[W; 256]; 8]) -> W {
+        let fold = |a: u128, b: u128, keys: [u64; 2]| {
+            clmul(a as u64, keys[0]) ^ clmul((a >> 64) as u64, keys[1]) ^ b
+        };
+        let mut blocks = buf
+            .chunks_exact(16)
+            .map(|block| u128::from_le_bytes(block.try_into().unwrap()));
+        let mut x = [0u128; 4];
+        for (x, block) in x.iter_mut().zip(&mut blocks) {
+            *x = block;
+        }
+        x[0] ^= crc.low_u64() as u128;
+        let mut blocks = blocks.collect::<std::vec::Vec<_>>();
+        while blocks.len() >= 4 {
+            for (i, block) in blocks.drain(..4).enumerate() {
+                x[i] = fold(x[i], block, keys.fold_512);
+            }
+        }
+        let folded = blocks
+            .into_iter()
+            .fold(x[1..].iter().fold(x[0], |a, b| fold(a, *b, keys.fold_128)), |a, b| {
+                fold(a, b, keys.fold_128)
+            });
+        let crc = crate::algorithm::update(W::ZERO, &folded.to_le_bytes(), lookup_table);
+        crate::algorithm::update(crc, &buf[buf.len() / 16 * 16..], lookup_table)
+    }
+
+    #[test]
+    fn folding_of_every_width_is_coherent_with_lookup_table() {
+        let mut buf = [0u8; 300];
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = (i * 31) as u8;
+        }
+        for len in [64, 100, 128, 200, 300] {
+            let buf = &buf[..len];
+
+            let lookup_table = generate_table_u8(0x07);
+            let keys = FoldKeys::new(0x07, 8);
+            assert_eq!(
+                fold_model(0x5Au8, buf, &keys, &lookup_table),
+                crate::algorithm::update(0x5A, buf, &lookup_table)
+            );
+
+            let lookup_table = generate_table_u16(0x8005);
+            let keys = FoldKeys::new(0x8005, 16);
+            assert_eq!(
+                fold_model(0x5A5Au16, buf, &keys, &lookup_table),
+                crate::algorithm::update(0x5A5A, buf, &lookup_table)
+            );
+
+            let lookup_table = generate_table(0x04C11DB7);
+            let keys = FoldKeys::new(0x04C11DB7, 32);
+            assert_eq!(
+                fold_model(!0u32, buf, &keys, &lookup_table),
+                crate::algorithm::update(!0, buf, &lookup_table)
+            );
+
+            let lookup_table = generate_table_u64(0x42F0E1EBA9EA3693);
+            let keys = FoldKeys::new(0x42F0E1EBA9EA3693, 64);
+            assert_eq!(
+                fold_model(!0u64, buf, &keys, &lookup_table),
+                crate::algorithm::update(!0, buf, &lookup_table)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn constants_of_crc32_are_correct() {
+        use super::FoldConstants;
+
+        // Constants from the Intel paper, also used by zlib-ng and crc32fast
+        const CONSTANTS: FoldConstants = FoldConstants::new(crate::crc32::POLYNOMIAL);
+        assert_eq!(CONSTANTS.k1, 0x154442bd4);
+        assert_eq!(CONSTANTS.k2, 0x1c6e41596);
+        assert_eq!(CONSTANTS.k3, 0x1751997d0);
+        assert_eq!(CONSTANTS.k4, 0x0ccaa009e);
+        assert_eq!(CONSTANTS.k5, 0x163cd6124);
+        assert_eq!(CONSTANTS.p, 0x1db710641);
+        assert_eq!(CONSTANTS.u, 0x1f7011641);
+    }
+}
@@ -0,0 +1,82 @@
+//!
+//! Folding with the `pclmulqdq` instruction.
+use super::FoldConstants;
+use core::arch::x86_64::*;
+
+/// Buffers shorter than 128 bytes are hashed with the lookup table
+const MIN_LEN: usize = 128;
+
+/// Updates the reflected CRC register with the buffer.
+/// The tail shorter than 16 bytes is hashed with the lookup table.
+///
+/// # Safety
+/// The CPU must support `pclmulqdq`.
+#[target_feature(enable = "pclmulqdq")]
+pub(crate) unsafe fn update(
+    crc: u32,
+    mut buf: &[u8],
+    constants: &FoldConstants,
+    lookup_table: &[[u32; 256]; 8],
+) -> u32 {
+    if buf.len() < MIN_LEN {
+        return crate::algorithm::update(crc, buf, lookup_table);
+    }
+
+    // Fold 4 x 128 bits at once
+    let mut x3 = load(&mut buf);
+    let mut x2 = load(&mut buf);
+    let mut x1 = load(&mut buf);
+    let mut x0 = load(&mut buf);
+    x3 = _mm_xor_si128(x3, _mm_cvtsi32_si128(crc as i32));
+
+    let k1k2 = _mm_set_epi64x(constants.k2 as i64, constants.k1 as i64);
+    while buf.len() >= 64 {
+        x3 = fold(x3, load(&mut buf), k1k2);
+        x2 = fold(x2, load(&mut buf), k1k2);
+        x1 = fold(x1, load(&mut buf), k1k2);
+        x0 = fold(x0, load(&mut buf), k1k2);
+    }
+
+    // Fold 4 x 128 bits into 128 bits, then the remaining 128 bits blocks
+    let k3k4 = _mm_set_epi64x(constants.k4 as i64, constants.k3 as i64);
+    let mut x = fold(x3, x2, k3k4);
+    x = fold(x, x1, k3k4);
+    x = fold(x, x0, k3k4);
+    while buf.len() >= 16 {
+        x = fold(x, load(&mut buf), k3k4);
+    }
+
+    // Reduce 128 bits to 96 bits, then to 64 bits
+    let low_32 = _mm_set_epi32(0, 0, 0, !0);
+    let x = _mm_xor_si128(_mm_clmulepi64_si128(x, k3k4, 0x10), _mm_srli_si128(x, 8));
+    let x = _mm_xor_si128(
+        _mm_clmulepi64_si128(_mm_and_si128(x, low_32), _mm_set_epi64x(0, constants.k5 as i64), 0x00),
+        _mm_srli_si128(x, 4),
+    );
+
+    // Barrett reduction of 64 bits to 32 bits
+    let pu = _mm_set_epi64x(constants.u as i64, constants.p as i64);
+    let t1 = _mm_clmulepi64_si128(_mm_and_si128(x, low_32), pu, 0x10);
+    let t2 = _mm_clmulepi64_si128(_mm_and_si128(t1, low_32), pu, 0x00);
+    let crc = _mm_cvtsi128_si32(_mm_srli_si128(_mm_xor_si128(x, t2), 4)) as u32;
+
+    // Consume remaining 0 to 15 bytes
+    crate::algorithm::update(crc, buf, lookup_table)
+}
+
+/// Loads 128 bits and advances the buffer
+#[inline(always)]
+unsafe fn load(buf: &mut &[u8]) -> __m128i {
+    let block = _mm_loadu_si128(buf.as_ptr() as *const __m128i);
+    *buf = &buf[16..];
+    block
+}
+
+/// Folds `a` over `b`: multiplies each half of `a` by its constant and adds `b`
+#[inline]
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn fold(a: __m128i, b: __m128i, keys: __m128i) -> __m128i {
+    let low = _mm_clmulepi64_si128(a, keys, 0x00);
+    let high = _mm_clmulepi64_si128(a, keys, 0x11);
+    _mm_xor_si128(_mm_xor_si128(b, low), high)
+}
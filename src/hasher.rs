@@ -0,0 +1,310 @@
+//!
+//! Slice-By-8 hashers shared by the CRC32, CRC32c and CRC64 modules.
+//! The hashers only differ by their register and their checksum, they are generated by [hasher].
+
+/// Implement a Slice-By-8 hasher, its builder of default hashers and its seeded builder.
+///
+/// The hasher updates its key with `crate::$module::slice_by_8_with_seed`.
+/// `write_u32` and `write_u64` default to the little endian bytes of the integer,
+/// they are overridden by functions updating the register with the integer.
+macro_rules! hasher {
+    (
+        $module:ident,
+        $hasher:ident,
+        $build_hasher:ident,
+        $seeded_build_hasher:ident,
+        $register:ty,
+        seed: $seed:literal
+        $(, write_u32: $update_u32:path, write_u64: $update_u64:path)?
+    ) => {
+        /// Slice-By-8 hasher
+        #[derive(Debug, Default)]
+        pub struct $hasher {
+            key: $register,
+        }
+
+        impl $hasher {
+            #[doc = concat!("Create a new [", stringify!($hasher), "] initiated with a hash key")]
+            pub fn with_seed(seed: $register) -> $hasher {
+                $hasher { key: seed }
+            }
+        }
+
+        impl core::hash::Hasher for $hasher {
+            /// Returns the hash value for the values written so far.
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!("use hud_slice_by_8::", stringify!($module), "::", stringify!($hasher), ";")]
+            /// use core::hash::Hasher;
+            ///
+            #[doc = concat!("let hasher = ", stringify!($hasher), "::with_seed(", stringify!($seed), ");")]
+            #[doc = concat!("assert_eq!(hasher.finish(), ", stringify!($seed), ");")]
+            /// ```
+            fn finish(&self) -> u64 {
+                crate::CrcWidth::low_u64(self.key)
+            }
+
+            #[doc = concat!("Writes some data into the [", stringify!($hasher), "].")]
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!("use hud_slice_by_8::", stringify!($module), "::", stringify!($hasher), ";")]
+            /// use core::hash::Hasher;
+            ///
+            #[doc = concat!("let mut hasher = ", stringify!($hasher), "::default();")]
+            /// hasher.write(b"hash me!");
+            /// ```
+            fn write(&mut self, bytes: &[u8]) {
+                self.key = crate::$module::slice_by_8_with_seed(bytes, self.key);
+            }
+
+            #[doc = concat!("Writes a `u8` into the [", stringify!($hasher), "].")]
+            fn write_u8(&mut self, i: u8) {
+                self.write(&[i]);
+            }
+
+            #[doc = concat!("Writes a `u16` in little endian order into the [", stringify!($hasher), "].")]
+            fn write_u16(&mut self, i: u16) {
+                self.write(&i.to_le_bytes());
+            }
+
+            crate::hasher::hasher!(@write_u32 $hasher $($update_u32)?);
+
+            crate::hasher::hasher!(@write_u64 $hasher $($update_u64)?);
+
+            #[doc = concat!("Writes a `u128` in little endian order into the [", stringify!($hasher), "].")]
+            fn write_u128(&mut self, i: u128) {
+                self.write_u64(i as u64);
+                self.write_u64((i >> 64) as u64);
+            }
+
+            #[doc = concat!("Writes a `usize` as a little endian `u64` into the [", stringify!($hasher), "],")]
+            /// so the hash is the same on 32 and 64 bit targets.
+            fn write_usize(&mut self, i: usize) {
+                self.write_u64(i as u64);
+            }
+        }
+
+        impl core::hash::BuildHasher for $hasher {
+            type Hasher = $hasher;
+
+            #[doc = concat!("Creates a new [", stringify!($hasher), "] initiated with the hash key.")]
+            fn build_hasher(&self) -> Self::Hasher {
+                $hasher::with_seed(self.key)
+            }
+        }
+
+        #[doc = concat!("A builder for default [", stringify!($hasher), "].")]
+        pub type $build_hasher = core::hash::BuildHasherDefault<$hasher>;
+
+        #[doc = concat!("A builder of [", stringify!($hasher), "] initiated with its seed.")]
+        ///
+        /// # Example
+        ///
+        /// ```
+        #[doc = concat!("use hud_slice_by_8::", stringify!($module), "::", stringify!($seeded_build_hasher), ";")]
+        /// use std::collections::HashMap;
+        ///
+        #[doc = concat!("let mut map = HashMap::with_hasher(", stringify!($seeded_build_hasher), "::new(", stringify!($seed), "));")]
+        /// map.insert("hash me!", "Hi");
+        /// assert_eq!(map.get("hash me!"), Some(&"Hi"));
+        /// ```
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct $seeded_build_hasher {
+            seed: $register,
+        }
+
+        impl $seeded_build_hasher {
+            #[doc = concat!("Create a new [", stringify!($seeded_build_hasher), "] building hashers initiated with the seed")]
+            pub const fn new(seed: $register) -> $seeded_build_hasher {
+                $seeded_build_hasher { seed }
+            }
+
+            #[doc = concat!("Create a new [", stringify!($seeded_build_hasher), "] with a random seed.")]
+            /// The seed is drawn from the randomness of [std::collections::hash_map::RandomState]
+            /// so the hashes of each instance differ, a basic mitigation of HashDoS.
+            /// CRC is not a cryptographic hash, the seed does not prevent targeted collisions.
+            #[cfg(feature = "std")]
+            pub fn random() -> $seeded_build_hasher {
+                use core::hash::{BuildHasher, Hasher};
+                let random = std::collections::hash_map::RandomState::new()
+                    .build_hasher()
+                    .finish();
+                // Fold the high bits into the low bits of narrow registers
+                let random = (random ^ (random >> 32)) as u128;
+                $seeded_build_hasher::new(crate::CrcWidth::from_u128(random))
+            }
+
+            /// Returns the seed of the built hashers
+            pub const fn seed(&self) -> $register {
+                self.seed
+            }
+        }
+
+        impl core::hash::BuildHasher for $seeded_build_hasher {
+            type Hasher = $hasher;
+
+            #[doc = concat!("Creates a new [", stringify!($hasher), "] initiated with the seed.")]
+            fn build_hasher(&self) -> Self::Hasher {
+                $hasher::with_seed(self.seed)
+            }
+        }
+    };
+    (@write_u32 $hasher:ident) => {
+        #[doc = concat!("Writes a `u32` in little endian order into the [", stringify!($hasher), "].")]
+        fn write_u32(&mut self, i: u32) {
+            self.write(&i.to_le_bytes());
+        }
+    };
+    (@write_u32 $hasher:ident $update_u32:path) => {
+        #[doc = concat!("Writes a `u32` in little endian order into the [", stringify!($hasher), "].")]
+        fn write_u32(&mut self, i: u32) {
+            self.key = !$update_u32(!self.key, i);
+        }
+    };
+    (@write_u64 $hasher:ident) => {
+        #[doc = concat!("Writes a `u64` in little endian order into the [", stringify!($hasher), "].")]
+        fn write_u64(&mut self, i: u64) {
+            self.write(&i.to_le_bytes());
+        }
+    };
+    (@write_u64 $hasher:ident $update_u64:path) => {
+        #[doc = concat!("Writes a `u64` in little endian order into the [", stringify!($hasher), "].")]
+        fn write_u64(&mut self, i: u64) {
+            self.key = !$update_u64(!self.key, i);
+        }
+    };
+}
+pub(crate) use hasher;
+
+#[cfg(test)]
+mod tests {
+    const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+    /// Test the hasher of a CRC module, `$check` is the checksum of [HASH_ME]
+    macro_rules! hasher_tests {
+        ($module:ident, $hasher:ident, $build_hasher:ident, $seeded_build_hasher:ident, check: $check:literal, seed: $seed:literal) => {
+            mod $module {
+                use super::HASH_ME;
+                use crate::$module::{self, $build_hasher, $hasher, $seeded_build_hasher};
+                use crate::CrcWidth;
+                use core::hash::{BuildHasher, Hasher};
+
+                #[test]
+                fn hasher_default() {
+                    let hasher = $hasher::default();
+                    assert_eq!(hasher.finish(), 0);
+                }
+
+                #[test]
+                fn hasher_with_seed() {
+                    let hasher = $hasher::with_seed($seed);
+                    assert_eq!(hasher.finish(), $seed);
+                }
+
+                #[test]
+                fn build_hasher() {
+                    let build_hasher = $hasher::default();
+                    let mut hasher = build_hasher.build_hasher();
+                    hasher.write(HASH_ME);
+                    assert_eq!(hasher.finish(), $check);
+                }
+
+                #[test]
+                fn keyed_build_hasher_differs_from_default() {
+                    let mut keyed = $hasher::with_seed($seed).build_hasher();
+                    let mut default = $hasher::default().build_hasher();
+                    keyed.write(HASH_ME);
+                    default.write(HASH_ME);
+                    assert_ne!(keyed.finish(), default.finish());
+                    assert_eq!(
+                        keyed.finish(),
+                        $module::slice_by_8_with_seed(HASH_ME, $seed).low_u64()
+                    );
+                }
+
+                #[test]
+                fn build_hasher_results_are_coherent_with_free_function() {
+                    let build_hasher = $hasher::default();
+                    let mut hasher = build_hasher.build_hasher();
+
+                    // First hash is the checksum without seed
+                    let hash_free_function = $module::slice_by_8(HASH_ME);
+                    hasher.write(HASH_ME);
+                    assert_eq!(hasher.finish(), hash_free_function.low_u64());
+
+                    // Second hash is the checksum seeded with the first hash
+                    let hash_free_function =
+                        $module::slice_by_8_with_seed(HASH_ME, hash_free_function);
+                    hasher.write(HASH_ME);
+                    assert_eq!(hasher.finish(), hash_free_function.low_u64());
+                }
+
+                #[test]
+                fn hasher_is_usable_in_std_collections() {
+                    extern crate std;
+                    use std::collections::HashMap;
+                    const HASH_ME: &str = "hash me!";
+                    const VALUE: &str = "Hi";
+
+                    let mut map = HashMap::with_hasher($build_hasher::default());
+                    map.insert(HASH_ME, VALUE);
+                    assert_eq!(map.get(&HASH_ME), Some(&VALUE));
+                }
+
+                #[test]
+                fn seeded_build_hasher_propagates_its_seed() {
+                    let build_hasher = $seeded_build_hasher::new(123456789);
+                    let mut hasher = build_hasher.build_hasher();
+                    hasher.write(HASH_ME);
+                    assert_eq!(
+                        hasher.finish(),
+                        $module::slice_by_8_with_seed(HASH_ME, 123456789).low_u64()
+                    );
+                    assert_eq!(build_hasher.build_hasher().finish(), 123456789);
+                }
+
+                #[test]
+                #[cfg(feature = "std")]
+                fn random_seeded_build_hasher_is_usable_in_std_collections() {
+                    extern crate std;
+                    use std::collections::HashMap;
+                    const HASH_ME: &str = "hash me!";
+                    const VALUE: &str = "Hi";
+
+                    let mut map = HashMap::with_hasher($seeded_build_hasher::random());
+                    map.insert(HASH_ME, VALUE);
+                    assert_eq!(map.get(&HASH_ME), Some(&VALUE));
+                }
+
+                #[test]
+                fn write_integers_is_little_endian() {
+                    let mut hasher = $hasher::default();
+                    hasher.write_u16(0x1234);
+                    hasher.write_u32(0x12345678);
+                    hasher.write_u64(0x123456789ABCDEF0);
+                    hasher.write_u128(0x0123456789ABCDEF_FEDCBA9876543210);
+                    hasher.write_usize(0x12345678);
+                    hasher.write_i8(-2);
+
+                    let mut bytes = [0u8; 2 + 4 + 8 + 16 + 8 + 1];
+                    bytes[..2].copy_from_slice(&[0x34, 0x12]);
+                    bytes[2..6].copy_from_slice(&[0x78, 0x56, 0x34, 0x12]);
+                    bytes[6..14].copy_from_slice(&[0xF0, 0xDE, 0xBC, 0x9A, 0x78, 0x56, 0x34, 0x12]);
+                    bytes[14..30]
+                        .copy_from_slice(&0x0123456789ABCDEF_FEDCBA9876543210u128.to_le_bytes());
+                    bytes[30..38].copy_from_slice(&[0x78, 0x56, 0x34, 0x12, 0, 0, 0, 0]);
+                    bytes[38] = 0xFE;
+                    assert_eq!(hasher.finish(), $module::slice_by_8(&bytes).low_u64());
+                }
+            }
+        };
+    }
+
+    hasher_tests!(crc32, CRC32Hasher, CRC32BuildHasher, CRC32SeededBuildHasher, check: 0x4C2750BD, seed: 0x9B9BEFFB);
+    hasher_tests!(crc32c, CRC32CHasher, CRC32CBuildHasher, CRC32CSeededBuildHasher, check: 0x9EE6EF25, seed: 0x9B9BEFFB);
+    hasher_tests!(crc64, CRC64Hasher, CRC64BuildHasher, CRC64SeededBuildHasher, check: 0x26967875751B122F, seed: 0x9B9BEFFB9B9BEFFB);
+}
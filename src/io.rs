@@ -0,0 +1,192 @@
+//!
+//! `std::io` adapters shared by the CRC32 and CRC32c modules.
+use crate::{Digest, Kernel};
+use core::fmt::{self, Debug};
+use std::io::{Read, Result, Write};
+
+/// Writer computing the CRC32 checksum of the [Kernel] over the data written to the inner writer
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::crc32;
+/// use std::io::Write;
+///
+/// let mut writer = crc32::CrcWriter::new(Vec::new());
+/// writer.write_all(b"abcdefghijklmnopqrstuvwxyz").unwrap();
+///
+/// assert_eq!(writer.crc(), 0x4C2750BD);
+/// assert_eq!(writer.into_inner(), b"abcdefghijklmnopqrstuvwxyz");
+/// ```
+pub struct CrcWriter<W, K> {
+    inner: W,
+    digest: Digest<K>,
+}
+
+impl<W, K: Kernel> CrcWriter<W, K> {
+    /// Create a new [CrcWriter] forwarding to the inner writer
+    pub fn new(inner: W) -> CrcWriter<W, K> {
+        CrcWriter {
+            inner,
+            digest: Digest::new(),
+        }
+    }
+
+    /// Returns the checksum of the data written so far
+    pub fn crc(&self) -> u32 {
+        self.digest.finalize()
+    }
+
+    /// Returns a reference to the inner writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    /// Data written directly to the inner writer is not checksummed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the [CrcWriter] and returns the inner writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Debug, K> Debug for CrcWriter<W, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CrcWriter")
+            .field("inner", &self.inner)
+            .field("digest", &self.digest)
+            .finish()
+    }
+}
+
+impl<W: Write, K: Kernel> Write for CrcWriter<W, K> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.digest.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reader computing the CRC32 checksum of the [Kernel] over the data read from the inner reader
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::crc32;
+/// use std::io::Read;
+///
+/// let mut reader = crc32::CrcReader::new(&b"abcdefghijklmnopqrstuvwxyz"[..]);
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf).unwrap();
+///
+/// assert_eq!(reader.crc(), 0x4C2750BD);
+/// ```
+pub struct CrcReader<R, K> {
+    inner: R,
+    digest: Digest<K>,
+}
+
+impl<R, K: Kernel> CrcReader<R, K> {
+    /// Create a new [CrcReader] forwarding to the inner reader
+    pub fn new(inner: R) -> CrcReader<R, K> {
+        CrcReader {
+            inner,
+            digest: Digest::new(),
+        }
+    }
+
+    /// Returns the checksum of the data read so far
+    pub fn crc(&self) -> u32 {
+        self.digest.finalize()
+    }
+
+    /// Returns a reference to the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    /// Data read directly from the inner reader is not checksummed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the [CrcReader] and returns the inner reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Debug, K> Debug for CrcReader<R, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CrcReader")
+            .field("inner", &self.inner)
+            .field("digest", &self.digest)
+            .finish()
+    }
+}
+
+impl<R: Read, K: Kernel> Read for CrcReader<R, K> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.digest.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CrcReader, CrcWriter};
+    use crate::crc32::CRC32Kernel;
+    use crate::crc32c::CRC32CKernel;
+    use crate::{crc32, crc32c, Kernel};
+    use std::io::{copy, Read, Write};
+    use std::vec::Vec;
+
+    const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog, 0123456789";
+
+    fn writer_is_coherent_with_slice_by_8<K: Kernel>(slice_by_8: fn(&[u8]) -> u32) {
+        let mut writer = CrcWriter::<_, K>::new(Vec::new());
+        for chunk in HASH_ME.chunks(7) {
+            writer.write_all(chunk).unwrap();
+        }
+        writer.flush().unwrap();
+        assert_eq!(writer.crc(), slice_by_8(HASH_ME));
+        assert_eq!(writer.into_inner(), HASH_ME);
+    }
+
+    fn reader_is_coherent_with_slice_by_8<K: Kernel>(slice_by_8: fn(&[u8]) -> u32) {
+        let mut reader = CrcReader::<_, K>::new(HASH_ME);
+        let mut buf = [0u8; 10];
+        assert_eq!(reader.read(&mut buf).unwrap(), 10);
+        assert_eq!(reader.crc(), slice_by_8(&HASH_ME[..10]));
+
+        let mut remaining = Vec::new();
+        reader.read_to_end(&mut remaining).unwrap();
+        assert_eq!(reader.crc(), slice_by_8(HASH_ME));
+    }
+
+    fn copy_checksums_both_ends<K: Kernel>(slice_by_8: fn(&[u8]) -> u32) {
+        let mut reader = CrcReader::<_, K>::new(HASH_ME);
+        let mut writer = CrcWriter::<_, K>::new(Vec::new());
+        copy(&mut reader, &mut writer).unwrap();
+        assert_eq!(reader.crc(), writer.crc());
+        assert_eq!(writer.crc(), slice_by_8(HASH_ME));
+    }
+
+    #[test]
+    fn adapters_are_coherent_with_slice_by_8() {
+        writer_is_coherent_with_slice_by_8::<CRC32Kernel>(crc32::slice_by_8);
+        writer_is_coherent_with_slice_by_8::<CRC32CKernel>(crc32c::slice_by_8);
+        reader_is_coherent_with_slice_by_8::<CRC32Kernel>(crc32::slice_by_8);
+        reader_is_coherent_with_slice_by_8::<CRC32CKernel>(crc32c::slice_by_8);
+        copy_checksums_both_ends::<CRC32Kernel>(crc32::slice_by_8);
+        copy_checksums_both_ends::<CRC32CKernel>(crc32c::slice_by_8);
+    }
+}
@@ -2,10 +2,93 @@
 #![no_std]
 //#![cfg_attr(all(target_arch = "aarch64", target_feature = "crc"), feature(stdsimd))]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod algorithm;
+pub use algorithm::generate_normal_table;
+pub use algorithm::generate_normal_table_u128;
+pub use algorithm::generate_normal_table_u16;
+pub use algorithm::generate_normal_table_u64;
+pub use algorithm::generate_normal_table_u8;
 pub use algorithm::generate_table;
+pub use algorithm::generate_table_u128;
+pub use algorithm::generate_table_u16;
+pub use algorithm::generate_table_u64;
+pub use algorithm::generate_table_u8;
 pub use algorithm::slice_by_8;
+pub use algorithm::slice_by_8_normal;
+pub use algorithm::slice_by_8_normal_with_seed;
 pub use algorithm::slice_by_8_with_seed;
 
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+mod async_io;
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub use async_io::{AsyncCrcReader, AsyncCrcWriter};
+
+mod combine;
+pub use combine::combine;
+
+mod crc;
+pub use crc::{
+    Crc, Crc128, Crc128Params, Crc16, Crc16Params, Crc64, Crc64Params, Crc8, Crc8Params,
+    CrcParams, GenericCrc, GenericCrcParams, CHECK_INPUT,
+};
+
+mod digest;
+pub use digest::{Digest, Kernel};
+
+#[cfg(all(
+    feature = "std",
+    any(
+        all(
+            target_arch = "x86_64",
+            not(all(target_feature = "pclmulqdq", target_feature = "sse4.2"))
+        ),
+        all(
+            target_arch = "aarch64",
+            not(all(target_feature = "aes", target_feature = "crc"))
+        )
+    )
+))]
+mod detect;
+
+#[cfg(any(
+    all(target_arch = "x86_64", any(target_feature = "pclmulqdq", feature = "std")),
+    all(target_arch = "aarch64", any(target_feature = "aes", feature = "std"))
+))]
+mod fold;
+#[cfg(feature = "memmap2")]
+mod file;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+pub use io::{CrcReader, CrcWriter};
+mod hasher;
+mod mix;
+mod models;
+#[cfg(feature = "rayon")]
+mod parallel;
+
+mod slicing;
+pub use slicing::bytewise;
+pub use slicing::bytewise_with_seed;
+pub use slicing::generate_table_bytewise;
+pub use slicing::generate_table_slice_by_16;
+pub use slicing::generate_table_slice_by_4;
+pub use slicing::slice_by_16;
+pub use slicing::slice_by_16_with_seed;
+pub use slicing::slice_by_4;
+pub use slicing::slice_by_4_with_seed;
+
+mod wide_hasher;
+pub use wide_hasher::{Lanes, WideHasher};
+
+mod width;
+pub use width::CrcWidth;
+
+pub mod crc16;
 pub mod crc32;
 pub mod crc32c;
+pub mod crc64;
+pub mod crc8;
@@ -0,0 +1,27 @@
+//!
+//! Finalizer of the 64 bit hashers.
+//! CRC are linear, the finalizer of MurmurHash3 spreads every bit of the hash over all the output bits
+//! so hash tables probing the high bits of the hash see well distributed values.
+
+/// Mixes the bits of the hash with the MurmurHash3 `fmix64` finalizer
+#[inline(always)]
+pub(crate) const fn fmix64(mut hash: u64) -> u64 {
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xFF51AFD7ED558CCD);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xC4CEB9FE1A85EC53);
+    hash ^ (hash >> 33)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fmix64;
+
+    #[test]
+    fn fmix64_spreads_bit_flips() {
+        assert_eq!(fmix64(0), 0);
+        assert_ne!(fmix64(1), fmix64(2));
+        // A single bit flip changes the high bits
+        assert_ne!(fmix64(1) >> 57, fmix64(0) >> 57);
+    }
+}
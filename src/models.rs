@@ -0,0 +1,111 @@
+//!
+//! Catalogue of CRC models shared by the CRC-8, CRC-16 and CRC-32 modules.
+
+/// Implement a model of the `$family` module with a register of type `$register`.
+///
+/// The model provides its parameters, a lookup table generated at compile time, a static engine
+/// and the `slice_by_8`, `slice_by_8_with_seed` and `combine` functions.
+/// Parameters are verified against their check value at compile time.
+macro_rules! model {
+    (
+        $family:ident: $register:ty,
+        $(#[$meta:meta])*
+        $module:ident,
+        poly: $poly:literal,
+        init: $init:literal,
+        refin: $refin:literal,
+        refout: $refout:literal,
+        xorout: $xorout:literal,
+        check: $check:literal
+    ) => {
+        $(#[$meta])*
+        pub mod $module {
+            use crate::{GenericCrc, GenericCrcParams};
+
+            /// Polynomial used to generate the [LOOKUP_TABLE]
+            pub const POLYNOMIAL: $register = $poly;
+
+            /// Parameters of the model
+            pub const PARAMS: GenericCrcParams<$register> = GenericCrcParams {
+                poly: POLYNOMIAL,
+                init: $init,
+                refin: $refin,
+                refout: $refout,
+                xorout: $xorout,
+                check: $check,
+            };
+
+            /// Engine of the model, built once at compile time
+            const ENGINE: GenericCrc<$register> = GenericCrc::<$register>::new(PARAMS);
+
+            /// Lookup table generated at compile time with the [POLYNOMIAL].
+            /// The table is reflected if [GenericCrcParams::refin] is set.
+            pub const LOOKUP_TABLE: [[$register; 256]; 8] = *ENGINE.lookup_table();
+
+            static CRC: GenericCrc<$register> = ENGINE;
+
+            /// Computes the checksum for the specified buffer using the slicing by 8
+            /// algorithm over 64 bit quantities.
+            ///
+            /// # Example
+            /// ```
+            #[doc = concat!("use hud_slice_by_8::", stringify!($family), "::", stringify!($module), ";")]
+            ///
+            #[doc = concat!(
+                "assert_eq!(",
+                stringify!($module),
+                "::slice_by_8(b\"123456789\"), ",
+                stringify!($check),
+                ");"
+            )]
+            /// ```
+            #[inline(always)]
+            pub fn slice_by_8(buf: &[u8]) -> $register {
+                CRC.checksum(buf)
+            }
+
+            /// Computes the checksum for the specified buffer using the slicing by 8
+            /// algorithm over 64 bit quantities, resuming from `seed`, the checksum of the previous data.
+            ///
+            /// # Example
+            /// ```
+            #[doc = concat!("use hud_slice_by_8::", stringify!($family), "::", stringify!($module), ";")]
+            ///
+            #[doc = concat!("let crc = ", stringify!($module), "::slice_by_8(b\"1234\");")]
+            #[doc = concat!(
+                "assert_eq!(",
+                stringify!($module),
+                "::slice_by_8_with_seed(b\"56789\", crc), ",
+                stringify!($check),
+                ");"
+            )]
+            /// ```
+            #[inline(always)]
+            pub fn slice_by_8_with_seed(buf: &[u8], seed: $register) -> $register {
+                CRC.checksum_with_seed(buf, seed)
+            }
+
+            /// Computes the checksum of the concatenation of two blocks from the checksum of each
+            /// block without hashing them again. `len_b` is the length of the second block.
+            ///
+            /// # Example
+            /// ```
+            #[doc = concat!("use hud_slice_by_8::", stringify!($family), "::", stringify!($module), ";")]
+            ///
+            #[doc = concat!("let crc_a = ", stringify!($module), "::slice_by_8(b\"1234\");")]
+            #[doc = concat!("let crc_b = ", stringify!($module), "::slice_by_8(b\"56789\");")]
+            #[doc = concat!(
+                "assert_eq!(",
+                stringify!($module),
+                "::combine(crc_a, crc_b, 5), ",
+                stringify!($check),
+                ");"
+            )]
+            /// ```
+            pub fn combine(crc_a: $register, crc_b: $register, len_b: u64) -> $register {
+                CRC.combine(crc_a, crc_b, len_b)
+            }
+        }
+    };
+}
+pub(crate) use model;
@@ -0,0 +1,32 @@
+//!
+//! Multi-threaded CRC over large buffers.
+//! The buffer is split in chunks hashed concurrently, CRC of chunks are then combined.
+use crate::CrcWidth;
+use rayon::prelude::*;
+
+/// Size of the chunks hashed by each task.
+/// Buffers smaller than a chunk are hashed on the calling thread.
+pub(crate) const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Computes the CRC of the buffer on the rayon thread pool.
+/// `slice_by_8` must return `0` for an empty buffer, as the crate's `slice_by_8` functions do.
+pub(crate) fn par_slice_by_8<W: CrcWidth + Send + Sync>(
+    buf: &[u8],
+    slice_by_8: impl Fn(&[u8]) -> W + Sync,
+    polynomial: W,
+) -> W {
+    if buf.len() <= CHUNK_SIZE {
+        return slice_by_8(buf);
+    }
+
+    let (crc, _len) = buf
+        .par_chunks(CHUNK_SIZE)
+        .map(|chunk| (slice_by_8(chunk), chunk.len() as u64))
+        .reduce(
+            || (W::ZERO, 0),
+            |(crc_a, len_a), (crc_b, len_b)| {
+                (crate::combine(crc_a, crc_b, len_b, polynomial), len_a + len_b)
+            },
+        );
+    crc
+}
@@ -0,0 +1,251 @@
+//!
+//! Variants of the slice-by-8 algorithm trading the size of the lookup table for the speed.
+//! Slice-by-16 uses a 16 KiB table and is faster on large cores,
+//! slice-by-4 uses a 4 KiB table and the byte-wise algorithm a 1 KiB table.
+//! The variants compute reflected 32 bit CRC.
+use crate::algorithm::generate_tables;
+
+/// Generate a 1 KiB lookup table for the [bytewise] algorithm.
+/// The given polynomial is reversed before the generation
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc32, generate_table_bytewise};
+///
+/// assert_eq!(generate_table_bytewise(crc32::POLYNOMIAL), crc32::LOOKUP_TABLE[0]);
+/// ```
+pub const fn generate_table_bytewise(polynomial: u32) -> [u32; 256] {
+    generate_tables::<1>(polynomial)[0]
+}
+
+/// Generate a 4 KiB lookup table for the [slice_by_4] algorithm.
+/// The given polynomial is reversed before the generation
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc32, generate_table_slice_by_4};
+///
+/// const LOOKUP_TABLE: [[u32; 256]; 4] = generate_table_slice_by_4(crc32::POLYNOMIAL);
+///
+/// assert_eq!(LOOKUP_TABLE[3], crc32::LOOKUP_TABLE[3]);
+/// ```
+pub const fn generate_table_slice_by_4(polynomial: u32) -> [[u32; 256]; 4] {
+    generate_tables(polynomial)
+}
+
+/// Generate a 16 KiB lookup table for the [slice_by_16] algorithm.
+/// The given polynomial is reversed before the generation
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc32, generate_table_slice_by_16};
+///
+/// const LOOKUP_TABLE: [[u32; 256]; 16] = generate_table_slice_by_16(crc32::POLYNOMIAL);
+///
+/// assert_eq!(LOOKUP_TABLE[7], crc32::LOOKUP_TABLE[7]);
+/// ```
+pub const fn generate_table_slice_by_16(polynomial: u32) -> [[u32; 256]; 16] {
+    generate_tables(polynomial)
+}
+
+/// Computes the CRC checksum for the specified buffer one byte at a time.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{bytewise, crc32, generate_table_bytewise};
+///
+/// const LOOKUP_TABLE: [u32; 256] = generate_table_bytewise(crc32::POLYNOMIAL);
+/// const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+///
+/// assert_eq!(bytewise(HASH_ME, &LOOKUP_TABLE), 0x4C2750BD);
+/// ```
+#[inline(always)]
+pub fn bytewise(buf: &[u8], lookup_table: &[u32; 256]) -> u32 {
+    bytewise_with_seed(buf, 0, lookup_table)
+}
+
+/// Computes the CRC checksum for the specified buffer one byte at a time, adding a seed to the result.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{bytewise_with_seed, crc32, generate_table_bytewise};
+///
+/// const LOOKUP_TABLE: [u32; 256] = generate_table_bytewise(crc32::POLYNOMIAL);
+/// const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+///
+/// assert_eq!(bytewise_with_seed(HASH_ME, 123456789, &LOOKUP_TABLE), 0xEADB5034);
+/// ```
+pub fn bytewise_with_seed(buf: &[u8], seed: u32, lookup_table: &[u32; 256]) -> u32 {
+    !buf.iter().fold(!seed, |acc, byte| {
+        (acc >> 8) ^ lookup_table[((acc ^ *byte as u32) & 0xFF) as usize]
+    })
+}
+
+/// Computes the CRC checksum for the specified buffer using the slicing by 4
+/// algorithm over 32 bit quantities.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc32, generate_table_slice_by_4, slice_by_4};
+///
+/// const LOOKUP_TABLE: [[u32; 256]; 4] = generate_table_slice_by_4(crc32::POLYNOMIAL);
+/// const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+///
+/// assert_eq!(slice_by_4(HASH_ME, &LOOKUP_TABLE), 0x4C2750BD);
+/// ```
+#[inline(always)]
+pub fn slice_by_4(buf: &[u8], lookup_table: &[[u32; 256]; 4]) -> u32 {
+    slice_by_4_with_seed(buf, 0, lookup_table)
+}
+
+/// Computes the CRC checksum for the specified buffer using the slicing by 4
+/// algorithm over 32 bit quantities, adding a seed to the result.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc32, generate_table_slice_by_4, slice_by_4_with_seed};
+///
+/// const LOOKUP_TABLE: [[u32; 256]; 4] = generate_table_slice_by_4(crc32::POLYNOMIAL);
+/// const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+///
+/// assert_eq!(slice_by_4_with_seed(HASH_ME, 123456789, &LOOKUP_TABLE), 0xEADB5034);
+/// ```
+pub fn slice_by_4_with_seed(buf: &[u8], seed: u32, lookup_table: &[[u32; 256]; 4]) -> u32 {
+    let mut crc = !seed;
+
+    // Consume all bytes until we are 4 bytes aligned
+    let (prefix, words, suffix) = unsafe { buf.align_to::<u32>() };
+    crc = prefix.iter().fold(crc, |acc, byte| {
+        (acc >> 8) ^ lookup_table[0][((acc ^ *byte as u32) & 0xFF) as usize]
+    });
+
+    // Process four bytes at once (Slicing-by-4)
+    // The first byte in memory is the least significant one whatever the endianness is
+    crc = words.iter().fold(crc, |acc, word| {
+        let word = u32::from_le(*word) ^ acc;
+        lookup_table[0][((word >> 24) & 0xFF) as usize]
+            ^ lookup_table[1][((word >> 16) & 0xFF) as usize]
+            ^ lookup_table[2][((word >> 8) & 0xFF) as usize]
+            ^ lookup_table[3][(word & 0xFF) as usize]
+    });
+
+    // Consume remaining 1 to 3 bytes (standard algorithm)
+    !suffix.iter().fold(crc, |acc, byte| {
+        (acc >> 8) ^ lookup_table[0][((acc ^ *byte as u32) & 0xFF) as usize]
+    })
+}
+
+/// Computes the CRC checksum for the specified buffer using the slicing by 16
+/// algorithm over 64 bit quantities.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc32, generate_table_slice_by_16, slice_by_16};
+///
+/// const LOOKUP_TABLE: [[u32; 256]; 16] = generate_table_slice_by_16(crc32::POLYNOMIAL);
+/// const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+///
+/// assert_eq!(slice_by_16(HASH_ME, &LOOKUP_TABLE), 0x4C2750BD);
+/// ```
+#[inline(always)]
+pub fn slice_by_16(buf: &[u8], lookup_table: &[[u32; 256]; 16]) -> u32 {
+    slice_by_16_with_seed(buf, 0, lookup_table)
+}
+
+/// Computes the CRC checksum for the specified buffer using the slicing by 16
+/// algorithm over 64 bit quantities, adding a seed to the result.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{crc32, generate_table_slice_by_16, slice_by_16_with_seed};
+///
+/// const LOOKUP_TABLE: [[u32; 256]; 16] = generate_table_slice_by_16(crc32::POLYNOMIAL);
+/// const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+///
+/// assert_eq!(slice_by_16_with_seed(HASH_ME, 123456789, &LOOKUP_TABLE), 0xEADB5034);
+/// ```
+pub fn slice_by_16_with_seed(buf: &[u8], seed: u32, lookup_table: &[[u32; 256]; 16]) -> u32 {
+    let mut crc = !seed;
+
+    // Consume all bytes until we are 8 bytes aligned
+    let (prefix, shorts, suffix) = unsafe { buf.align_to::<u64>() };
+    crc = prefix.iter().fold(crc, |acc, byte| {
+        (acc >> 8) ^ lookup_table[0][((acc ^ *byte as u32) & 0xFF) as usize]
+    });
+
+    // Process sixteen bytes at once (Slicing-by-16)
+    // The first byte in memory is the least significant one whatever the endianness is
+    let mut chunks = shorts.chunks_exact(2);
+    crc = chunks.by_ref().fold(crc, |acc, chunk| {
+        let first = u64::from_le(chunk[0]) ^ acc as u64;
+        let second = u64::from_le(chunk[1]);
+        let (first_low, first_high) = (first as u32, (first >> u32::BITS) as u32);
+        let (second_low, second_high) = (second as u32, (second >> u32::BITS) as u32);
+        lookup_table[0][((second_high >> 24) & 0xFF) as usize]
+            ^ lookup_table[1][((second_high >> 16) & 0xFF) as usize]
+            ^ lookup_table[2][((second_high >> 8) & 0xFF) as usize]
+            ^ lookup_table[3][(second_high & 0xFF) as usize]
+            ^ lookup_table[4][((second_low >> 24) & 0xFF) as usize]
+            ^ lookup_table[5][((second_low >> 16) & 0xFF) as usize]
+            ^ lookup_table[6][((second_low >> 8) & 0xFF) as usize]
+            ^ lookup_table[7][(second_low & 0xFF) as usize]
+            ^ lookup_table[8][((first_high >> 24) & 0xFF) as usize]
+            ^ lookup_table[9][((first_high >> 16) & 0xFF) as usize]
+            ^ lookup_table[10][((first_high >> 8) & 0xFF) as usize]
+            ^ lookup_table[11][(first_high & 0xFF) as usize]
+            ^ lookup_table[12][((first_low >> 24) & 0xFF) as usize]
+            ^ lookup_table[13][((first_low >> 16) & 0xFF) as usize]
+            ^ lookup_table[14][((first_low >> 8) & 0xFF) as usize]
+            ^ lookup_table[15][(first_low & 0xFF) as usize]
+    });
+
+    // Process the remaining eight bytes at once (Slicing-by-8), the first 8 tables are the slice-by-8 tables
+    crc = chunks.remainder().iter().fold(crc, |acc, short| {
+        let short = u64::from_le(*short) ^ acc as u64;
+        let (low, high) = (short as u32, (short >> u32::BITS) as u32);
+        lookup_table[0][((high >> 24) & 0xFF) as usize]
+            ^ lookup_table[1][((high >> 16) & 0xFF) as usize]
+            ^ lookup_table[2][((high >> 8) & 0xFF) as usize]
+            ^ lookup_table[3][(high & 0xFF) as usize]
+            ^ lookup_table[4][((low >> 24) & 0xFF) as usize]
+            ^ lookup_table[5][((low >> 16) & 0xFF) as usize]
+            ^ lookup_table[6][((low >> 8) & 0xFF) as usize]
+            ^ lookup_table[7][(low & 0xFF) as usize]
+    });
+
+    // Consume remaining 1 to 7 bytes (standard algorithm)
+    !suffix.iter().fold(crc, |acc, byte| {
+        (acc >> 8) ^ lookup_table[0][((acc ^ *byte as u32) & 0xFF) as usize]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as slice_by_8;
+    use slice_by_8::crc32::{LOOKUP_TABLE, POLYNOMIAL};
+
+    #[test]
+    fn tables_are_coherent_with_slice_by_8_table() {
+        const LOOKUP_TABLE_16: [[u32; 256]; 16] = slice_by_8::generate_table_slice_by_16(POLYNOMIAL);
+        assert_eq!(slice_by_8::generate_table_bytewise(POLYNOMIAL), LOOKUP_TABLE[0]);
+        assert_eq!(slice_by_8::generate_table_slice_by_4(POLYNOMIAL), LOOKUP_TABLE[..4]);
+        assert_eq!(LOOKUP_TABLE_16[..8], LOOKUP_TABLE);
+    }
+
+    #[test]
+    fn variants_are_coherent_with_slice_by_8() {
+        const LOOKUP_TABLE_1: [u32; 256] = slice_by_8::generate_table_bytewise(POLYNOMIAL);
+        const LOOKUP_TABLE_4: [[u32; 256]; 4] = slice_by_8::generate_table_slice_by_4(POLYNOMIAL);
+        const LOOKUP_TABLE_16: [[u32; 256]; 16] = slice_by_8::generate_table_slice_by_16(POLYNOMIAL);
+        const HASH_ME: &[u8] = b"The quick brown fox jumps over the lazy dog, 0123456789";
+        for start in 0..16 {
+            for end in start..HASH_ME.len() {
+                let buf = &HASH_ME[start..end];
+                let expected = slice_by_8::slice_by_8_with_seed(buf, 123456789, &LOOKUP_TABLE);
+                assert_eq!(slice_by_8::bytewise_with_seed(buf, 123456789, &LOOKUP_TABLE_1), expected);
+                assert_eq!(slice_by_8::slice_by_4_with_seed(buf, 123456789, &LOOKUP_TABLE_4), expected);
+                assert_eq!(slice_by_8::slice_by_16_with_seed(buf, 123456789, &LOOKUP_TABLE_16), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//!
+//! Slice-By-8 hasher with a 64 bit output shared by the CRC32 and CRC32c modules.
+//! The hasher has two 32 bit lanes mixed by a finalizer.
+use core::fmt::{self, Debug};
+use core::hash::Hasher;
+use core::marker::PhantomData;
+
+/// CRC of the two lanes of a [WideHasher]
+///
+/// Implemented by [CRC32Lanes](crate::crc32::CRC32Lanes) and [CRC32CLanes](crate::crc32c::CRC32CLanes).
+pub trait Lanes {
+    /// Updates the lane seeded by the low 32 bits
+    fn key(bytes: &[u8], key: u32) -> u32;
+
+    /// Updates the lane seeded by the high 32 bits
+    fn other_key(bytes: &[u8], other_key: u32) -> u32;
+}
+
+/// Slice-By-8 hasher with a 64 bit output
+///
+/// Both lanes are mixed by a finalizer so every bit of the 64 bit output depends on the data,
+/// unlike the 32 bit hashers whose high 32 bits are always cleared.
+///
+/// Every write runs the kernels of both lanes over the data,
+/// a write costs about the time of the two 32 bit hashers.
+pub struct WideHasher<L: Lanes> {
+    key: u32,
+    other_key: u32,
+    lanes: PhantomData<fn() -> L>,
+}
+
+impl<L: Lanes> WideHasher<L> {
+    /// Create a new hasher initiated with a hash key.
+    /// The low 32 bits seed the first lane, the high 32 bits seed the other lane.
+    pub fn with_seed(seed: u64) -> WideHasher<L> {
+        WideHasher {
+            key: seed as u32,
+            other_key: (seed >> 32) as u32,
+            lanes: PhantomData,
+        }
+    }
+}
+
+impl<L: Lanes> Default for WideHasher<L> {
+    fn default() -> WideHasher<L> {
+        WideHasher::with_seed(0)
+    }
+}
+
+impl<L: Lanes> Debug for WideHasher<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WideHasher")
+            .field("key", &self.key)
+            .field("other_key", &self.other_key)
+            .finish()
+    }
+}
+
+impl<L: Lanes> Hasher for WideHasher<L> {
+    /// Returns the hash value for the values written so far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hud_slice_by_8::crc32::CRC32WideHasher;
+    /// use core::hash::Hasher;
+    ///
+    /// let mut hasher = CRC32WideHasher::default();
+    /// hasher.write(b"hash me!");
+    /// assert_ne!(hasher.finish() >> 32, 0);
+    /// ```
+    fn finish(&self) -> u64 {
+        crate::mix::fmix64(((self.other_key as u64) << 32) | self.key as u64)
+    }
+
+    /// Writes some data into the hasher.
+    fn write(&mut self, bytes: &[u8]) {
+        self.key = L::key(bytes, self.key);
+        self.other_key = L::other_key(bytes, self.other_key);
+    }
+
+    /// Writes a `u16` in little endian order into the hasher.
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    /// Writes a `u32` in little endian order into the hasher.
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    /// Writes a `u64` in little endian order into the hasher.
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    /// Writes a `u128` in little endian order into the hasher.
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    /// Writes a `usize` as a little endian `u64` into the hasher,
+    /// so the hash is the same on 32 and 64 bit targets.
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crc32::{CRC32WideBuildHasher, CRC32WideHasher};
+    use crate::crc32c::{CRC32CWideBuildHasher, CRC32CWideHasher};
+    use crate::{crc32, crc32c};
+    use core::hash::{BuildHasher, Hasher};
+
+    const HASH_ME: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+    #[test]
+    fn wide_hasher_lanes_are_coherent_with_free_functions() {
+        let crc32 = |seed| crc32::slice_by_8_with_seed(HASH_ME, seed) as u64;
+        let crc32c = |seed| crc32c::slice_by_8_with_seed(HASH_ME, seed) as u64;
+
+        let mut hasher = CRC32WideHasher::with_seed(123456789 << 32 | 987654321);
+        hasher.write(HASH_ME);
+        let expected = crc32c(123456789) << 32 | crc32(987654321);
+        assert_eq!(hasher.finish(), crate::mix::fmix64(expected));
+
+        let mut hasher = CRC32CWideHasher::with_seed(123456789 << 32 | 987654321);
+        hasher.write(HASH_ME);
+        let expected = crc32(123456789) << 32 | crc32c(987654321);
+        assert_eq!(hasher.finish(), crate::mix::fmix64(expected));
+    }
+
+    #[test]
+    fn wide_hasher_write_usize_is_write_u64() {
+        let (mut a, mut b) = (CRC32WideHasher::default(), CRC32WideHasher::default());
+        a.write_usize(0x12345678);
+        b.write(&0x12345678u64.to_le_bytes());
+        assert_eq!(a.finish(), b.finish());
+    }
+
+    #[test]
+    fn wide_hasher_sets_high_bits() {
+        let high_bits = (0..64u8).fold(0, |high_bits, i| {
+            let mut hasher = CRC32WideBuildHasher::default().build_hasher();
+            hasher.write(&[i]);
+            high_bits | hasher.finish() >> 57
+        });
+        assert_eq!(high_bits, 0x7F);
+    }
+
+    #[test]
+    fn wide_hasher_is_usable_in_std_collections() {
+        extern crate std;
+        use std::collections::HashMap;
+        const HASH_ME: &str = "hash me!";
+        const VALUE: &str = "Hi";
+
+        let mut map = HashMap::with_hasher(CRC32CWideBuildHasher::default());
+        map.insert(HASH_ME, VALUE);
+        assert_eq!(map.get(&HASH_ME), Some(&VALUE));
+    }
+}
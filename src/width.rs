@@ -0,0 +1,145 @@
+//!
+//! Register widths supported by the slice-by-8 algorithm.
+use crate::algorithm::{
+    generate_normal_table, generate_normal_table_u128, generate_normal_table_u16,
+    generate_normal_table_u64, generate_normal_table_u8, generate_table, generate_table_u128,
+    generate_table_u16, generate_table_u64, generate_table_u8,
+};
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::ops::{BitXor, Not};
+
+/// Register of a CRC processed by the slice-by-8 algorithm
+///
+/// The trait is implemented for `u8`, `u16`, `u32`, `u64` and `u128`.
+/// The width of the CRC is the number of bits of the register.
+///
+/// # Example
+/// ```
+/// use hud_slice_by_8::{slice_by_8, CrcWidth};
+///
+/// fn checksum<W: CrcWidth>(buf: &[u8], polynomial: W) -> W {
+///     slice_by_8(buf, &W::generate_table(polynomial))
+/// }
+///
+/// assert_eq!(checksum(b"123456789", 0x04C11DB7u32), 0xCBF43926);
+/// assert_eq!(checksum(b"123456789", 0x42F0E1EBA9EA3693u64), 0x995DC9BBDF1939FA);
+/// ```
+pub trait CrcWidth: Copy + Debug + Eq + Hash + BitXor<Output = Self> + Not<Output = Self> {
+    /// Number of bits of the register
+    const BITS: u32;
+
+    /// Register with all bits cleared
+    const ZERO: Self;
+
+    /// Returns the least significant 64 bits of the register
+    fn low_u64(self) -> u64;
+
+    /// Converts the register to a `u128`
+    fn to_u128(self) -> u128;
+
+    /// Truncates a `u128` to the register
+    fn from_u128(value: u128) -> Self;
+
+    /// Returns the most significant 64 bits of the register.
+    /// Registers narrower than 64 bits are aligned on the most significant bit.
+    fn high_u64(self) -> u64;
+
+    /// Shifts the register to the right, every bit is shifted out if `bits` is at least [CrcWidth::BITS]
+    fn shift_right(self, bits: u32) -> Self;
+
+    /// Shifts the register to the left, every bit is shifted out if `bits` is at least [CrcWidth::BITS]
+    fn shift_left(self, bits: u32) -> Self;
+
+    /// Reverses the order of bits of the register
+    fn reverse_bits(self) -> Self;
+
+    /// Generate a reflected (LSB-first) lookup table.
+    /// The given polynomial is reversed before the generation
+    fn generate_table(polynomial: Self) -> [[Self; 256]; 8];
+
+    /// Generate a non-reflected (MSB-first) lookup table.
+    fn generate_normal_table(polynomial: Self) -> [[Self; 256]; 8];
+}
+
+macro_rules! impl_crc_width {
+    ($ty:ty, $generate_table:ident, $generate_normal_table:ident, |$register:ident| $high_u64:expr) => {
+        impl CrcWidth for $ty {
+            const BITS: u32 = <$ty>::BITS;
+
+            const ZERO: Self = 0;
+
+            #[inline(always)]
+            fn low_u64(self) -> u64 {
+                self as u64
+            }
+
+            #[inline(always)]
+            fn to_u128(self) -> u128 {
+                self as u128
+            }
+
+            #[inline(always)]
+            fn from_u128(value: u128) -> Self {
+                value as $ty
+            }
+
+            #[inline(always)]
+            fn high_u64(self) -> u64 {
+                let $register = self;
+                $high_u64
+            }
+
+            #[inline(always)]
+            fn shift_right(self, bits: u32) -> Self {
+                self.checked_shr(bits).unwrap_or(0)
+            }
+
+            #[inline(always)]
+            fn shift_left(self, bits: u32) -> Self {
+                self.checked_shl(bits).unwrap_or(0)
+            }
+
+            #[inline(always)]
+            fn reverse_bits(self) -> Self {
+                <$ty>::reverse_bits(self)
+            }
+
+            fn generate_table(polynomial: Self) -> [[Self; 256]; 8] {
+                $generate_table(polynomial)
+            }
+
+            fn generate_normal_table(polynomial: Self) -> [[Self; 256]; 8] {
+                $generate_normal_table(polynomial)
+            }
+        }
+    };
+}
+
+impl_crc_width!(u8, generate_table_u8, generate_normal_table_u8, |register| (register as u64) << 56);
+impl_crc_width!(u16, generate_table_u16, generate_normal_table_u16, |register| (register as u64) << 48);
+impl_crc_width!(u32, generate_table, generate_normal_table, |register| (register as u64) << 32);
+impl_crc_width!(u64, generate_table_u64, generate_normal_table_u64, |register| register);
+impl_crc_width!(u128, generate_table_u128, generate_normal_table_u128, |register| (register >> 64) as u64);
+
+#[cfg(test)]
+mod tests {
+    use super::CrcWidth;
+
+    #[test]
+    fn high_u64_is_aligned_on_most_significant_bit() {
+        assert_eq!(0x80u8.high_u64(), 1 << 63);
+        assert_eq!(0x8000u16.high_u64(), 1 << 63);
+        assert_eq!(0x80000000u32.high_u64(), 1 << 63);
+        assert_eq!(0x8000000000000000u64.high_u64(), 1 << 63);
+        assert_eq!((1u128 << 127).high_u64(), 1 << 63);
+    }
+
+    #[test]
+    fn shift_out_every_bit_clears_the_register() {
+        assert_eq!(0xFFu8.shift_right(8), 0);
+        assert_eq!(0xFFu8.shift_left(8), 0);
+        assert_eq!(u64::MAX.shift_right(64), 0);
+        assert_eq!(u128::MAX.shift_right(64), u64::MAX as u128);
+    }
+}